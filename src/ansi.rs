@@ -0,0 +1,143 @@
+//! Module converting the `[[EFFECT_*]]` markup into ANSI escape sequences, to preview widgets outside of Rtop
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::ansi::{self, ColorMode};
+//! use rtop_dev::components::bar::{Color, Horizontal};
+//!
+//! let bar = Horizontal::new(1, 10, Some(Color::Blue));
+//! let output = ansi::render(&bar.display(50.0), ColorMode::Ansi16);
+//!
+//! assert_eq!(output, "\x1b[34;40m█████     \x1b[0m\n");
+//! print!("{}", ansi::render(&bar.display(50.0), ColorMode::detect())); // Display the bar in the terminal
+//! ```
+
+use crate::frame::Frame;
+use crate::markup;
+use crate::style::{Color, Style};
+use crate::text;
+
+/// Represent the colors supported by a terminal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ColorMode {
+    /// The 16 standard colors, supported by every terminal
+    Ansi16,
+    /// The 256 colors palette, using the colors of the 6x6x6 cube and of the grayscale ramp which don't depend on the theme of the terminal
+    Ansi256,
+    /// 24-bit RGB colors, which don't depend on the theme of the terminal
+    TrueColor,
+}
+
+impl ColorMode {
+    /// # Return the best mode supported by the current terminal, according to the `COLORTERM` and `TERM` environment variables
+    pub fn detect() -> Self {
+        let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+        let term = std::env::var("TERM").unwrap_or_default();
+
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+/// # Convert a string using the `[[EFFECT_*]]` markers into text using ANSI escape sequences
+/// Unknown markers are kept as text, see [`markup::validate`] to detect them.
+/// Control characters of the text are replaced by a space, see [`text::sanitize`], so the widget can't write its own escape sequences
+/// ## Arguments
+/// * `markup` - The output of a widget or a component
+/// * `mode` - The colors supported by the terminal
+pub fn render(markup: &str, mode: ColorMode) -> String {
+    let lines = markup::parse(markup)
+        .lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| paint(&text::sanitize(&span.content), span.style, mode))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>();
+
+    let mut out = lines.join("\n");
+    if markup.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+/// # Convert a [`Frame`] into text using ANSI escape sequences
+/// ## Arguments
+/// * `frame` - The frame to convert
+/// * `mode` - The colors supported by the terminal
+pub fn render_frame(frame: &Frame, mode: ColorMode) -> String {
+    render(&frame.to_markup(), mode)
+}
+
+/// Return `text` surrounded by the sequences enabling and resetting `style`
+fn paint(text: &str, style: Style, mode: ColorMode) -> String {
+    let mut codes = vec![];
+    let modifiers = [
+        (style.modifiers.bold, "1"),
+        (style.modifiers.italic, "3"),
+        (style.modifiers.underline, "4"),
+        (style.modifiers.reverse, "7"),
+    ];
+    for (enabled, code) in modifiers {
+        if enabled {
+            codes.push(String::from(code));
+        }
+    }
+    codes.extend(color_code(style.fg, false, mode));
+    codes.extend(color_code(style.bg, true, mode));
+
+    if codes.is_empty() {
+        String::from(text)
+    } else {
+        format!("\x1b[{}m{}\x1b[0m", codes.join(";"), text)
+    }
+}
+
+/// Return the parameters of the SGR sequence selecting `color`, `None` for the default color
+fn color_code(color: Color, background: bool, mode: ColorMode) -> Option<String> {
+    let index = match color {
+        Color::Default => return None,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::White => 7,
+    };
+    let prefix = if background { 4 } else { 3 };
+
+    Some(match mode {
+        ColorMode::Ansi16 => format!("{}{}", prefix, index),
+        ColorMode::Ansi256 => format!("{}8;5;{}", prefix, PALETTE_256[index]),
+        ColorMode::TrueColor => {
+            let (r, g, b) = PALETTE[index];
+            format!("{}8;2;{};{};{}", prefix, r, g, b)
+        }
+    })
+}
+
+/// RGB values of the standard colors, as displayed by xterm
+const PALETTE: [(u8, u8, u8); 8] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+];
+
+/// Index of the colors of `PALETTE` in the 256 colors palette, the closest ones of the 6x6x6 cube or of the grayscale ramp
+const PALETTE_256: [u8; 8] = [16, 160, 40, 184, 21, 164, 44, 254];
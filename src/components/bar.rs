@@ -5,35 +5,51 @@
 //! use rtop_dev::components::bar::*;
 //!
 //!
-//! let bar = Horizontal::new(3, 24, Some(Color::Blue)); // Create a blue Horizontal ProgressBar with 3 cells of height and 24 cells of width
+//! let mut bar = Horizontal::new(3, 24, Some(Color::Blue)); // Create a blue Horizontal ProgressBar with 3 cells of height and 24 cells of width
 //! bar.display(50.0);
+//!
+//! bar.set_range(0.0, 8.0); // Display a value between 0 and 8, such as a load average
+//! bar.display(12.5); // Full, with an overflow indicator at its end
+//! bar.display(f32::NAN); // No data, the bar is dashed
 //! ```
 
-use std::fmt::Write;
+use crate::frame::Frame;
+use crate::style::{self, Style};
 
 /// Vertical `ProgressBar`, designed to be ready to use in Rtop
 pub struct Vertical {
     rows: i32,
     cols: i32,
     color: Color,
+    range: (f32, f32),
+    overflow_indicator: bool,
 }
 impl Vertical {
     /// # Return a formatted String ready to be display in Rtop
+    /// Always made of `rows` lines of `cols` cells
     /// ## Arguments
-    /// * `pourcent` - Represent the progress of the bar<br>
-    /// **⚠️ The `pourcent` must be between 0.0 and 100.0**
+    /// * `pourcent` - Represent the progress of the bar, clamped to the range of the bar. `NaN` is displayed as no data
     pub fn display(&self, pourcent: f32) -> String {
-        let color = match self.color {
-            Color::Red => "[[EFFECT_COLOR_RED_BLACK]]",
-            Color::Green => "[[EFFECT_COLOR_GREEN_BLACK]]",
-            Color::Yellow => "[[EFFECT_COLOR_YELLOW_BLACK]]",
-            Color::Blue => "[[EFFECT_COLOR_BLUE_BLACK]]",
-            Color::Magenta => "[[EFFECT_COLOR_MAGENTA_BLACK]]",
-            Color::Cyan => "[[EFFECT_COLOR_CYAN_BLACK]]",
-            Color::White => "[[EFFECT_COLOR_WHITE_BLACK]]",
-        };
+        let mut frame = Frame::new(self.rows, self.cols);
+        self.render(pourcent, &mut frame, 0, 0);
+        let out = frame.to_markup();
+
+        super::assert_size("Vertical", &out, self.rows, self.cols);
+        out
+    }
+
+    /// # Render the bar into a [`Frame`]
+    /// The bar fills the `rows` x `cols` cells from (`row`, `col`), rising from the bottom row. Cells outside of the frame are clipped
+    /// ## Arguments
+    /// * `pourcent` - Represent the progress of the bar
+    /// * `frame` - The frame to render into
+    /// * `row` - The row of the top of the bar
+    /// * `col` - The column of the left side of the bar
+    pub fn render(&self, pourcent: f32, frame: &mut Frame, row: i32, col: i32) {
+        let style = Style::new()
+            .fg(style::Color::from(self.color))
+            .bg(style::Color::Black);
 
-        let mut out = String::new();
         let mut bar_parts = std::collections::HashMap::new();
         bar_parts.insert(0, " ");
         bar_parts.insert(1, "▁");
@@ -45,29 +61,66 @@ impl Vertical {
         bar_parts.insert(7, "▇");
         bar_parts.insert(8, "█");
 
-        if pourcent == 100. {
-            out += &format!("{}{}{}\n", color, "█".repeat(self.cols as usize), color)
-                .repeat(self.rows as usize);
-        } else {
-            let block_filled = (self.rows as f32 * 8. * (pourcent / 100.)) as i32;
-            let white_lines =
-                (self.rows - 1 - (self.rows as f32 * (pourcent / 100.)) as i32) as usize;
-
-            out += &format!("{}{}{}\n", color, " ".repeat(self.cols as usize), color)
-                .repeat(white_lines);
-            writeln!(
-                &mut out,
-                "{}{}{}",
-                color,
-                bar_parts[&(block_filled % 8)].repeat(self.cols as usize),
-                color
-            )
-            .unwrap();
-            out += &format!("{}{}{}\n", color, "█".repeat(self.cols as usize), color)
-                .repeat(self.rows as usize - white_lines - 1);
+        let rows = self.rows.max(0);
+        let cols = self.cols.max(0) as usize;
+        let Some((block_filled, overflow)) = fill(pourcent, self.range, rows) else {
+            for y in row..row + rows {
+                frame.print(y, col, &"╎".repeat(cols), style);
+            }
+            return;
+        };
+
+        for (y, level) in (row..).zip((0..rows).rev()) {
+            let part = if level < block_filled / 8 {
+                bar_parts[&8]
+            } else if level == block_filled / 8 {
+                bar_parts[&(block_filled % 8)]
+            } else {
+                bar_parts[&0]
+            };
+            let part = if overflow && self.overflow_indicator && level == rows - 1 {
+                "▲"
+            } else {
+                part
+            };
+            frame.print(y, col, &part.repeat(cols), style);
         }
+    }
 
-        out
+    /// # Resize the `Vertical` to `rows` x `cols` cells, then display it
+    /// A bar keeps nothing else depending on its size, so resizing it on every call costs nothing
+    /// ## Arguments
+    /// * `pourcent` - Represent the progress of the bar
+    /// * `rows` - The height allocated to the bar
+    /// * `cols` - The width allocated to the bar
+    pub fn display_sized(&mut self, pourcent: f32, rows: i32, cols: i32) -> String {
+        self.resize(rows, cols);
+        self.display(pourcent)
+    }
+
+    /// # Resize the `Vertical`
+    /// ## Arguments
+    /// * `rows` - The new height of the bar
+    /// * `cols` - The new width of the bar
+    pub fn resize(&mut self, rows: i32, cols: i32) {
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    /// # Set the values displayed as an empty and a full bar, `0.0` and `100.0` by default
+    /// Values outside of the range are clamped to it. If `min` and `max` are equal, the bar is full once the value reaches them, and empty otherwise
+    /// ## Arguments
+    /// * `min` - The value of an empty bar
+    /// * `max` - The value of a full bar
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.range = (min.min(max), min.max(max));
+    }
+
+    /// # Enable or disable the indicator displayed at the end of a full bar when the value exceeds the range, enabled by default
+    /// ## Arguments
+    /// * `enabled` - `true` to display the indicator
+    pub fn set_overflow_indicator(&mut self, enabled: bool) {
+        self.overflow_indicator = enabled;
     }
 
     /// # Create a new `Vertical`
@@ -80,6 +133,8 @@ impl Vertical {
             rows,
             cols,
             color: color.unwrap_or(Color::Green),
+            range: DEFAULT_RANGE,
+            overflow_indicator: true,
         }
     }
 }
@@ -89,24 +144,35 @@ pub struct Horizontal {
     rows: i32,
     cols: i32,
     color: Color,
+    range: (f32, f32),
+    overflow_indicator: bool,
 }
 impl Horizontal {
     /// # Return a formatted String ready to be display in Rtop
+    /// Always made of `rows` lines of `cols` cells
     /// ## Arguments
-    /// * `pourcent` - Represent the progress of the bar<br>
-    /// **⚠️ The `pourcent` must be between 0.0 and 100.0**
+    /// * `pourcent` - Represent the progress of the bar, clamped to the range of the bar. `NaN` is displayed as no data
     pub fn display(&self, pourcent: f32) -> String {
-        let color = match self.color {
-            Color::Red => "[[EFFECT_COLOR_RED_BLACK]]",
-            Color::Green => "[[EFFECT_COLOR_GREEN_BLACK]]",
-            Color::Yellow => "[[EFFECT_COLOR_YELLOW_BLACK]]",
-            Color::Blue => "[[EFFECT_COLOR_BLUE_BLACK]]",
-            Color::Magenta => "[[EFFECT_COLOR_MAGENTA_BLACK]]",
-            Color::Cyan => "[[EFFECT_COLOR_CYAN_BLACK]]",
-            Color::White => "[[EFFECT_COLOR_WHITE_BLACK]]",
-        };
+        let mut frame = Frame::new(self.rows, self.cols);
+        self.render(pourcent, &mut frame, 0, 0);
+        let out = frame.to_markup();
+
+        super::assert_size("Horizontal", &out, self.rows, self.cols);
+        out
+    }
+
+    /// # Render the bar into a [`Frame`]
+    /// The same line, filled from the left, is written on each of the `rows` rows starting at `row`. Cells outside of the frame are clipped
+    /// ## Arguments
+    /// * `pourcent` - Represent the progress of the bar
+    /// * `frame` - The frame to render into
+    /// * `row` - The row of the top of the bar
+    /// * `col` - The column of the left side of the bar
+    pub fn render(&self, pourcent: f32, frame: &mut Frame, row: i32, col: i32) {
+        let style = Style::new()
+            .fg(style::Color::from(self.color))
+            .bg(style::Color::Black);
 
-        let mut out = String::new();
         let mut bar_parts = std::collections::HashMap::new();
         bar_parts.insert(0, " ");
         bar_parts.insert(1, "▏");
@@ -118,30 +184,61 @@ impl Horizontal {
         bar_parts.insert(7, "▉");
         bar_parts.insert(8, "█");
 
-        let block_filled = (self.cols as f32 * 8. * (pourcent / 100.)) as i32;
-        let white_lines = (self.cols - 1 - (self.cols as f32 * (pourcent / 100.)) as i32) as usize;
-
-        if pourcent == 100. {
-            out += &format!(
-                "{}{}{}\n",
-                color,
-                bar_parts[&8].repeat(self.cols as usize),
-                color
-            )
-            .repeat(self.rows as usize);
-        } else {
-            out += &format!(
-                "{}{}{}{}{}\n",
-                color,
+        let cols = self.cols.max(0);
+        let line = match fill(pourcent, self.range, cols) {
+            None => "╌".repeat(cols as usize),
+            Some((_, true)) if self.overflow_indicator && cols > 0 => {
+                format!("{}▶", bar_parts[&8].repeat((cols - 1) as usize))
+            }
+            Some((block_filled, _)) if block_filled / 8 == cols => {
+                bar_parts[&8].repeat(cols as usize)
+            }
+            Some((block_filled, _)) => format!(
+                "{}{}{}",
                 bar_parts[&8].repeat((block_filled / 8) as usize),
                 bar_parts[&(block_filled % 8)],
-                " ".repeat(white_lines),
-                color
-            )
-            .repeat(self.rows as usize);
+                " ".repeat((cols - block_filled / 8 - 1) as usize)
+            ),
+        };
+        for y in row..row + self.rows.max(0) {
+            frame.print(y, col, &line, style);
         }
+    }
 
-        out
+    /// # Resize the `Horizontal` to `rows` x `cols` cells, then display it
+    /// Useful for a bar taking the whole widget: pass the `height` and `width` received by [`Widget::display`](crate::widget::Widget::display)
+    /// ## Arguments
+    /// * `pourcent` - Represent the progress of the bar
+    /// * `rows` - The height allocated to the bar
+    /// * `cols` - The width allocated to the bar
+    pub fn display_sized(&mut self, pourcent: f32, rows: i32, cols: i32) -> String {
+        self.resize(rows, cols);
+        self.display(pourcent)
+    }
+
+    /// # Resize the `Horizontal`
+    /// ## Arguments
+    /// * `rows` - The new height of the bar
+    /// * `cols` - The new width of the bar
+    pub fn resize(&mut self, rows: i32, cols: i32) {
+        self.rows = rows;
+        self.cols = cols;
+    }
+
+    /// # Set the values displayed as an empty and a full bar, `0.0` and `100.0` by default
+    /// Values outside of the range are clamped to it. If `min` and `max` are equal, the bar is full once the value reaches them, and empty otherwise
+    /// ## Arguments
+    /// * `min` - The value of an empty bar
+    /// * `max` - The value of a full bar
+    pub fn set_range(&mut self, min: f32, max: f32) {
+        self.range = (min.min(max), min.max(max));
+    }
+
+    /// # Enable or disable the indicator displayed at the end of a full bar when the value exceeds the range, enabled by default
+    /// ## Arguments
+    /// * `enabled` - `true` to display the indicator
+    pub fn set_overflow_indicator(&mut self, enabled: bool) {
+        self.overflow_indicator = enabled;
     }
 
     /// # Create a new `Horizontal`
@@ -154,12 +251,40 @@ impl Horizontal {
             rows,
             cols,
             color: color.unwrap_or(Color::Green),
+            range: DEFAULT_RANGE,
+            overflow_indicator: true,
         }
     }
 }
 
+/// Default range of the bars, to display a percentage
+const DEFAULT_RANGE: (f32, f32) = (0., 100.);
+
+/// Return the number of eighths of cells filled by `value` on a bar of `cells` cells, and `true` if the value exceeds the range.
+/// Return `None` if the value is `NaN`
+fn fill(value: f32, range: (f32, f32), cells: i32) -> Option<(i32, bool)> {
+    if value.is_nan() {
+        return None;
+    }
+
+    // Compared first, so a range of zero width or an infinite value never divides zero or infinity
+    let (min, max) = range;
+    let ratio = if value >= max {
+        1.
+    } else if value <= min {
+        0.
+    } else {
+        (value - min) / (max - min)
+    };
+    Some((
+        ((cells as f32 * 8. * ratio) as i32).min(cells * 8),
+        value > max,
+    ))
+}
+
 /// Represent a Color of progress for the Bar
 #[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Color {
     Red,
     Green,
@@ -9,9 +9,13 @@
 //! let mut chart = Chart::new(0, 0, Some(20), Some(true), Some(String::from("Out of 20")));
 //! chart.resize(50, 25); // Resize the chart with a width of 50 and a height of 25 cells
 //! let result = chart.display(&data);
+//!
+//! let result = chart.display_sized(&data, 10, 40); // Resize the chart to 10 x 40 cells before displaying it
 //! ```
 
-use std::fmt::Write;
+use crate::frame::Frame;
+use crate::style::Style;
+use crate::text;
 
 /// Represent a sheet of data in the form of a graph
 pub struct Chart {
@@ -33,17 +37,34 @@ pub struct Chart {
 
 impl Chart {
     /// # Create the chart and return a formatted string ready to be displayed in Rtop
+    /// Always made of `rows` lines of `cols` cells, the chart is empty if there is no data
     /// ## Arguments
     /// * `percents` - List of data used to create the graph
     pub fn display(&self, percents: &[i32]) -> String {
-        let mut data = if percents.len() >= (self.cols * 2) as usize {
-            percents[percents.len() - (self.cols * 2) as usize..].to_vec()
+        let mut frame = Frame::new(self.rows, self.cols);
+        self.render(percents, &mut frame, 0, 0);
+        let output = frame.to_markup();
+
+        super::assert_size("Chart", &output, self.rows, self.cols);
+        output
+    }
+
+    /// # Render the Chart into a [`Frame`]
+    /// With `show_unit`, the last value takes the first row, at `row`, and the graph the rows below it. Every cell of the Chart is written, including the blank ones
+    /// ## Arguments
+    /// * `percents` - List of data used to create the graph
+    /// * `frame` - The frame to render into
+    /// * `row` - The row of the top of the Chart
+    /// * `col` - The column of the left side of the Chart
+    pub fn render(&self, percents: &[i32], frame: &mut Frame, row: i32, col: i32) {
+        let max_len = self.cols.max(0) as usize * 2;
+        let mut data = if percents.len() >= max_len {
+            percents[percents.len() - max_len..].to_vec()
         } else {
             percents.to_vec()
         };
         data.reverse();
 
-        let space_to_add = self.cols as f32 - (data.len() as f32 / 2.);
         let mut chart_chars: std::collections::HashMap<&str, String> =
             std::collections::HashMap::new();
 
@@ -91,91 +112,91 @@ impl Chart {
             self.rows
         };
 
-        let mut final_graph = String::new();
-        if !data.is_empty() {
-            if self.show_unit {
-                writeln!(
-                    &mut final_graph,
-                    "{}{}{}",
-                    " ".repeat(
-                        (self.cols
-                            - data[0].to_string().chars().count() as i32
-                            - self.unit_suffix.chars().count() as i32)
-                            as usize
-                    ),
-                    data[0],
-                    self.unit_suffix
-                )
-                .unwrap();
-            }
-
-            let mut tmp = vec![];
-
-            for row in 0..graph_rows {
-                let mut i = 0;
-                while i < data.len() {
-                    let percent_one = data[i];
-                    let mut tmp_one =
-                        percent_one as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
-                    if tmp_one < 1. {
-                        tmp_one = 1.;
-                    }
-                    let mut full_block_to_add_one = tmp_one as i32 - (4 * row);
-
-                    let mut percent_two = 0;
-                    let mut tmp_two =
-                        percent_two as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
-                    if i as usize + 1 < data.len() {
-                        percent_two = data[i + 1];
-                        tmp_two =
-                            percent_two as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
-                        if tmp_two < 1. {
-                            tmp_two = 1.;
-                        }
-                    }
+        let cols = self.cols.max(0) as usize;
+        let mut y = row;
+        if self.show_unit && self.rows > 0 {
+            let unit = data
+                .first()
+                .map(|value| format!("{}{}", value, self.unit_suffix))
+                .unwrap_or_default();
+            frame.print(y, col, &text::pad_left(&unit, cols), Style::NONE);
+            y += 1;
+        }
 
-                    let mut full_block_to_add_two = tmp_two as i32 - (4 * row);
+        let mut tmp = vec![];
 
-                    if full_block_to_add_one < 0 {
-                        full_block_to_add_one = 0;
-                    }
-                    if full_block_to_add_two < 0 {
-                        full_block_to_add_two = 0;
+        for row in 0..graph_rows {
+            let mut i = 0;
+            while i < data.len() {
+                let percent_one = data[i];
+                let mut tmp_one =
+                    percent_one as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
+                if tmp_one < 1. {
+                    tmp_one = 1.;
+                }
+                let mut full_block_to_add_one = tmp_one as i32 - (4 * row);
+
+                let mut percent_two = 0;
+                let mut tmp_two =
+                    percent_two as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
+                if i + 1 < data.len() {
+                    percent_two = data[i + 1];
+                    tmp_two = percent_two as f32 / self.higher_value as f32 * self.rows as f32 * 4.;
+                    if tmp_two < 1. {
+                        tmp_two = 1.;
                     }
+                }
 
-                    if full_block_to_add_one > 4 {
-                        full_block_to_add_one = 4;
-                    }
-                    if full_block_to_add_two > 4 {
-                        full_block_to_add_two = 4;
-                    }
+                let mut full_block_to_add_two = tmp_two as i32 - (4 * row);
 
-                    graph = format!(
-                        "{}{}",
-                        graph,
-                        chart_chars
-                            [&*format!("{}{}", full_block_to_add_two, full_block_to_add_one)]
-                    );
-                    i += 2;
+                if full_block_to_add_one < 0 {
+                    full_block_to_add_one = 0;
+                }
+                if full_block_to_add_two < 0 {
+                    full_block_to_add_two = 0;
                 }
 
-                tmp.push(graph);
-                graph = String::new();
-            }
+                if full_block_to_add_one > 4 {
+                    full_block_to_add_one = 4;
+                }
+                if full_block_to_add_two > 4 {
+                    full_block_to_add_two = 4;
+                }
 
-            tmp.reverse();
-            for line in tmp {
-                writeln!(
-                    &mut final_graph,
+                graph = format!(
                     "{}{}",
-                    " ".repeat(space_to_add as usize),
-                    line.chars().rev().collect::<String>()
-                )
-                .unwrap();
+                    graph,
+                    chart_chars[&*format!("{}{}", full_block_to_add_two, full_block_to_add_one)]
+                );
+                i += 2;
             }
+
+            tmp.push(graph);
+            graph = String::new();
+        }
+
+        tmp.reverse();
+        for (y, line) in (y..).zip(tmp) {
+            frame.print(
+                y,
+                col,
+                &text::pad_left(&line.chars().rev().collect::<String>(), cols),
+                Style::NONE,
+            );
         }
+    }
 
-        final_graph
+    /// # Resize the Chart to `height` x `width` cells if its size changed, then display it
+    /// Unlike `resize`, the height comes first, in the same order as the arguments of [`Widget::display`](crate::widget::Widget::display)
+    /// ## Arguments
+    /// * `percents` - List of data used to create the graph
+    /// * `height` - The height allocated to the Chart
+    /// * `width` - The width allocated to the Chart
+    pub fn display_sized(&mut self, percents: &[i32], height: i32, width: i32) -> String {
+        if (self.rows, self.cols) != (height, width) {
+            self.resize(width, height);
+        }
+        self.display(percents)
     }
 
     /// # Resize the Chart
@@ -22,13 +22,25 @@
 //! let mut listview = ListView::new(50, 25, &items, String::from("Name"), vec!(String::from("key1")), None, None);
 //!
 //! listview.display();
+//! listview.update_items(&items);
+//! assert!(!listview.needs_redraw()); // The items didn't change, the previous output can be reused
 //!
 //! // ...
 //!
 //! listview.sort_by(Some(String::from("key1")), Some(Ordering::Inversed)); // Sort by "key1" in descending order
+//!
+//! // ...
+//!
+//! listview.on_key(&rtop_dev::event::KeyEvent::parse("KEY_DOWN")); // Select the next item
+//!
+//! use rtop_dev::event::{MouseButton, MouseEvent, MouseKind};
+//! listview.on_mouse(&MouseEvent::new(MouseKind::Press(MouseButton::Left), 0, 0)); // Sort by "Name" by clicking on the header
 //! ```
 
-use std::fmt::Write;
+use crate::event::{Key, KeyEvent, MouseButton, MouseEvent, MouseKind};
+use crate::frame::Frame;
+use crate::style::Style;
+use crate::text;
 
 /// Display list of [`ListItem`] with table header, ordering and other stuffs
 pub struct ListView {
@@ -42,6 +54,7 @@ pub struct ListView {
     sort_key: Option<String>,
     counter: i32,
     ordering: Option<Ordering>,
+    changed: bool,
 }
 
 impl ListView {
@@ -75,6 +88,7 @@ impl ListView {
             start_index: 0,
             sort_key,
             ordering,
+            changed: true,
         };
         created_listview.sort();
         created_listview
@@ -83,6 +97,7 @@ impl ListView {
     /// # Select the previous element if possible
     pub fn previous(&mut self) {
         if self.counter > 0 {
+            self.changed = true;
             self.counter -= 1;
             if self.selected_line > 1 {
                 self.selected_line -= 1;
@@ -95,6 +110,7 @@ impl ListView {
     /// # Select the next element if possible
     pub fn next(&mut self) {
         if self.counter < self.items.len() as i32 - 1 {
+            self.changed = true;
             self.counter += 1;
             if self.selected_line == self.rows - 1 {
                 self.start_index += 1;
@@ -106,6 +122,7 @@ impl ListView {
 
     /// # Select the last element
     pub fn to_last(&mut self) {
+        self.changed = true;
         self.counter = self.items.len() as i32 - 1;
         self.selected_line = self.counter + 1;
         if self.selected_line > self.rows - 1 {
@@ -116,168 +133,148 @@ impl ListView {
 
     /// # Select the first element
     pub fn to_first(&mut self) {
+        self.changed = true;
         self.counter = 0;
         self.selected_line = 1;
         self.start_index = 0;
     }
 
+    /// # Move the selection according to the pressed key
+    /// Handle the arrows, `Home`, `End`, `PageUp` and `PageDown` keys. Return `true` if the key was handled
+    /// ## Arguments
+    /// * `key` - The key pressed by the user
+    pub fn on_key(&mut self, key: &KeyEvent) -> bool {
+        match key.key {
+            Key::Up => self.previous(),
+            Key::Down => self.next(),
+            Key::Home => self.to_first(),
+            Key::End => self.to_last(),
+            Key::PageUp => {
+                for _ in 0..(self.rows - 1).max(1) {
+                    self.previous();
+                }
+            }
+            Key::PageDown => {
+                for _ in 0..(self.rows - 1).max(1) {
+                    self.next();
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// # Return `true` if the output of `display` changed since its last call
+    /// Can be returned by [`Widget::needs_redraw`](crate::widget::Widget::needs_redraw), updating the `ListView` with the same items doesn't require a redraw
+    pub fn needs_redraw(&self) -> bool {
+        self.changed
+    }
+
     /// # Create the List and return a formatted String ready to be displayed in Rtop
+    /// Always made of `rows` lines of `cols` cells. Columns are aligned according to the display width of their content, and truncated when they don't fit
     pub fn display(&mut self) -> String {
-        let mut secondary_keys_len = std::collections::HashMap::new();
-        for key in &self.secondary_keys {
-            secondary_keys_len.insert(key.to_string(), key.len() + 2);
-        }
+        let mut frame = Frame::new(self.rows, self.cols);
+        self.render(&mut frame, 0, 0);
+        let output_string = frame.to_markup();
 
-        let mut displayed_items = &*self.items;
+        super::assert_size("ListView", &output_string, self.rows, self.cols);
+        output_string
+    }
 
-        for item in displayed_items {
-            for key_value in &item.data {
-                if secondary_keys_len.contains_key(key_value.0) {
-                    let tmp = secondary_keys_len[key_value.0];
-                    if key_value.1.len() + 2 > tmp {
-                        *secondary_keys_len.get_mut(key_value.0).unwrap() = key_value.1.len() + 2;
-                    }
-                }
-            }
+    /// # Render the List into a [`Frame`]
+    /// The header is written on `row` and the visible items below it, the selected one in reverse video. Like `display`, it resets `needs_redraw`
+    /// ## Arguments
+    /// * `frame` - The frame to render into
+    /// * `row` - The row of the header of the `ListView`
+    /// * `col` - The column of the left side of the `ListView`
+    pub fn render(&mut self, frame: &mut Frame, row: i32, col: i32) {
+        self.changed = false;
+        self.scroll_to_selection();
+        let columns = self.columns();
+
+        if self.rows <= 0 {
+            return;
+        }
+        let mut x = col;
+        for (key, width) in &columns {
+            frame.print(row, x, &text::pad(key, *width), Style::NONE);
+            frame.print(row, x, &text::truncate(key, *width), self.header_style(key));
+            x += *width as i32;
         }
 
-        let mut secondary_cols = String::new();
-        for key in &self.secondary_keys {
-            if let Some(sort_key) = &self.sort_key {
-                if key == sort_key {
-                    if let Some(ordering) = &self.ordering {
-                        secondary_cols += &if ordering == &Ordering::Default {
-                            format!(
-                                "[[EFFECT_BOLD]]{}[[EFFECT_BOLD]]{}",
-                                key,
-                                " ".repeat(secondary_keys_len[key] - key.len())
-                            )
-                        } else {
-                            format!(
-                                "[[EFFECT_ITALIC]]{}[[EFFECT_ITALIC]]{}",
-                                key,
-                                " ".repeat(secondary_keys_len[key] - key.len())
-                            )
-                        };
-                    } else {
-                        write!(
-                            &mut secondary_cols,
-                            "{}{}",
-                            key,
-                            " ".repeat(secondary_keys_len[key] - key.len())
-                        )
-                        .unwrap();
-                    }
+        let start = (self.start_index.max(0) as usize).min(self.items.len());
+        let end = (start + (self.rows - 1) as usize).min(self.items.len());
+        let displayed_items = &self.items[start..end];
+        for (i, item) in (1..self.rows).zip(displayed_items) {
+            let mut line = String::new();
+            for (j, (key, width)) in columns.iter().enumerate() {
+                let value = if j == 0 {
+                    &item.name
                 } else {
-                    write!(
-                        &mut secondary_cols,
-                        "{}{}",
-                        key,
-                        " ".repeat(secondary_keys_len[key] - key.len())
-                    )
-                    .unwrap();
-                }
-            } else {
-                write!(
-                    &mut secondary_cols,
-                    "{}{}",
-                    key,
-                    " ".repeat(secondary_keys_len[key] - key.len())
-                )
-                .unwrap();
+                    item.data.get(*key).map_or("", String::as_str)
+                };
+                line += &text::pad(value, *width);
             }
-        }
 
-        let mut ordering_spaces_header = 0;
-        if let Some(sort_key) = &self.sort_key {
-            if &self.primary_key != sort_key && self.secondary_keys.contains(sort_key) {
-                if let Some(ordering) = &self.ordering {
-                    ordering_spaces_header = match ordering {
-                        Ordering::Default => 30,
-                        Ordering::Inversed => 32,
-                    };
-                }
-            }
+            let style = if i == self.selected_line {
+                Style::new().reverse()
+            } else {
+                Style::NONE
+            };
+            frame.print(row + i, col, &line, style);
         }
 
-        let mut table_header_primary_col = String::from(&self.primary_key);
-        if let Some(sort_key) = &self.sort_key {
-            if &self.primary_key == sort_key {
-                if let Some(ordering) = &self.ordering {
-                    table_header_primary_col = if ordering == &Ordering::Default {
-                        format!("[[EFFECT_BOLD]]{}[[EFFECT_BOLD]]", self.primary_key)
-                    } else {
-                        format!("[[EFFECT_ITALIC]]{}[[EFFECT_ITALIC]]", self.primary_key)
-                    };
-                }
-            }
+        let blank_line = " ".repeat(self.cols.max(0) as usize);
+        for y in row + 1 + displayed_items.len() as i32..row + self.rows {
+            frame.print(y, col, &blank_line, Style::NONE);
         }
+    }
 
-        let mut output_string = format!(
-            "{}{}{}\n",
-            table_header_primary_col,
-            " ".repeat(self.cols as usize - self.primary_key.len() - secondary_cols.len() + ordering_spaces_header),
-            secondary_cols
-        );
-        if displayed_items.len() > (self.rows - 1) as usize {
-            displayed_items =
-                &self.items[self.start_index as usize..(self.start_index + self.rows - 1) as usize];
-        }
-        let mut i = 1;
-
-        for item in displayed_items {
-            let name = item
-                .name
-                .chars()
-                .into_iter()
-                .take(self.cols as usize - secondary_cols.len() + ordering_spaces_header)
-                .collect::<String>();
-            if i == self.selected_line {
-                write!(
-                    &mut output_string,
-                    "[[EFFECT_REVERSE]]{}{}",
-                    name,
-                    " ".repeat(
-                        self.cols as usize - name.chars().count() - secondary_cols.len() + ordering_spaces_header
-                    )
-                )
-                .unwrap();
-            } else {
-                write!(
-                    &mut output_string,
-                    "{}{}",
-                    name,
-                    " ".repeat(
-                        self.cols as usize - name.chars().count() - secondary_cols.len() + ordering_spaces_header
-                    )
-                )
-                .unwrap();
+    /// # Handle the mouse over the `ListView`
+    /// Scrolling move the selection, clicking an item select it and clicking a column of the header sort the items by this column.
+    /// Clicking the sorting column again inverse the ordering. Return `true` if the event was handled
+    /// ## Arguments
+    /// * `event` - The mouse event, with coordinates relative to the `ListView`
+    pub fn on_mouse(&mut self, event: &MouseEvent) -> bool {
+        match event.kind {
+            MouseKind::ScrollUp => self.previous(),
+            MouseKind::ScrollDown => self.next(),
+            MouseKind::Press(MouseButton::Left) if event.y == 0 => {
+                let Some(key) = self.column_at(event.x) else {
+                    return false;
+                };
+                let ordering = match (&self.sort_key, &self.ordering) {
+                    (Some(sort_key), Some(Ordering::Default)) if sort_key == &key => {
+                        Ordering::Inversed
+                    }
+                    _ => Ordering::Default,
+                };
+                self.sort_by(Some(key), Some(ordering));
             }
-
-            for col in &self.secondary_keys {
-                let len = secondary_keys_len[col];
-
-                if item.data.contains_key(col) {
-                    write!(
-                        &mut output_string,
-                        "{}{}",
-                        item.data[col],
-                        " ".repeat(len - item.data[col].len())
-                    )
-                    .unwrap();
-                } else {
-                    output_string.push_str(&" ".repeat(len));
+            MouseKind::Press(MouseButton::Left) if event.y > 0 && event.y < self.rows => {
+                let index = self.start_index + event.y - 1;
+                if index >= self.items.len() as i32 {
+                    return false;
                 }
+                self.changed = true;
+                self.counter = index;
+                self.selected_line = event.y;
             }
+            _ => return false,
+        }
+        true
+    }
 
-            if i == self.selected_line {
-                output_string += "[[EFFECT_REVERSE]]";
-            }
-            output_string += "\n";
-
-            i += 1;
+    /// # Resize the `ListView` to `rows` x `cols` cells if its size changed, then display it
+    /// As long as the size doesn't change, the scroll position is kept and `needs_redraw` only reports changes of the items or of the selection
+    /// ## Arguments
+    /// * `rows` - The height allocated to the `ListView`
+    /// * `cols` - The width allocated to the `ListView`
+    pub fn display_sized(&mut self, rows: i32, cols: i32) -> String {
+        if (self.rows, self.cols) != (rows, cols) {
+            self.resize(rows, cols);
         }
-        output_string
+        self.display()
     }
 
     /// # Resize the `ListView`
@@ -285,12 +282,10 @@ impl ListView {
     /// * `rows` - The new height of the `ListView`
     /// * `cols` - The new width of the `ListView`
     pub fn resize(&mut self, rows: i32, cols: i32) {
+        self.changed = true;
         self.rows = rows;
         self.cols = cols;
-        if self.selected_line > self.rows - 1 {
-            self.selected_line = self.rows - 1;
-            self.start_index = self.counter - (self.rows - 2);
-        }
+        self.scroll_to_selection();
     }
 
     /// # Update the list of `ListItem` contained in the `ListView`
@@ -299,12 +294,13 @@ impl ListView {
     /// **⚠️ The `items` must include the same `primary_key` and `secondary_keys` as the previous set of [`ListItem`]**
     pub fn update_items(&mut self, items: &[ListItem]) {
         if items.len() < self.counter as usize + 1 {
-            self.start_index -= self.counter + 1 - items.len() as i32;
-            self.counter -= self.counter + 1 - items.len() as i32;
+            self.changed = true;
         }
-        self.items = items.to_vec();
+        let previous_items = std::mem::replace(&mut self.items, items.to_vec());
 
         self.sort();
+        self.scroll_to_selection();
+        self.changed |= previous_items != self.items;
     }
 
     /// # Return the current selected `ListItem`
@@ -313,14 +309,97 @@ impl ListView {
     }
 
     /// # Update the way of sorting and sort the items
+    /// The selected item stays selected, and the list scrolls to its new position
     /// ## Arguments
     /// * `key` - *`Optional`* - If provided, update the sorting key
     /// * `ordering` - *`Optional`* - If provided, update the sorting order
     pub fn sort_by(&mut self, key: Option<String>, ordering: Option<Ordering>) {
+        self.changed = true;
         self.sort_key = key;
         self.ordering = ordering;
 
+        let selected = self.items.get(self.counter as usize).cloned();
         self.sort();
+        if let Some(index) = selected.and_then(|selected| self.items.iter().position(|item| *item == selected)) {
+            self.counter = index as i32;
+        }
+        self.scroll_to_selection();
+    }
+
+    /// Keep the selected item in the list and on screen, scrolling back if the items below it no longer fill the `ListView`
+    fn scroll_to_selection(&mut self) {
+        let visible = (self.rows - 1).max(1);
+        let len = self.items.len() as i32;
+        self.counter = self.counter.clamp(0, (len - 1).max(0));
+        self.start_index = self
+            .start_index
+            .min(len - visible)
+            .clamp(self.counter - visible + 1, self.counter)
+            .max(0);
+        self.selected_line = self.counter - self.start_index + 1;
+    }
+
+    /// Return the style of the title of a column, bold or italic if the items are sorted by this column
+    fn header_style(&self, key: &str) -> Style {
+        match (&self.sort_key, &self.ordering) {
+            (Some(sort_key), Some(Ordering::Default)) if sort_key == key => Style::new().bold(),
+            (Some(sort_key), Some(Ordering::Inversed)) if sort_key == key => Style::new().italic(),
+            _ => Style::NONE,
+        }
+    }
+
+    /// Return the title and the width of every column, starting with the primary column, clipped to the width of the `ListView`
+    fn columns(&self) -> Vec<(&str, usize)> {
+        let secondary_keys_len = self.secondary_keys_len();
+        let mut remaining = self.cols.max(0) as usize;
+        let primary_width = remaining.saturating_sub(
+            self.secondary_keys
+                .iter()
+                .map(|key| secondary_keys_len[key])
+                .sum(),
+        );
+
+        let mut columns = vec![(&*self.primary_key, primary_width)];
+        remaining -= primary_width;
+        for key in &self.secondary_keys {
+            let width = secondary_keys_len[key].min(remaining);
+            remaining -= width;
+            columns.push((&**key, width));
+        }
+        columns
+    }
+
+    fn secondary_keys_len(&self) -> std::collections::HashMap<String, usize> {
+        let mut secondary_keys_len = std::collections::HashMap::new();
+        for key in &self.secondary_keys {
+            secondary_keys_len.insert(key.clone(), text::width(key) + 2);
+        }
+
+        for item in &self.items {
+            for key_value in &item.data {
+                if let Some(len) = secondary_keys_len.get_mut(key_value.0) {
+                    *len = (*len).max(text::width(key_value.1) + 2);
+                }
+            }
+        }
+
+        secondary_keys_len
+    }
+
+    fn column_at(&self, x: i32) -> Option<String> {
+        if x < 0 {
+            return None;
+        }
+
+        let mut end = 0;
+        for (key, width) in self.columns() {
+            end += width as i32;
+            if x < end {
+                return Some(String::from(key));
+            }
+        }
+
+        None
     }
 
     fn sort(&mut self) {
@@ -352,7 +431,7 @@ impl ListView {
 }
 
 /// Represent an item of a [`ListView`]
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct ListItem {
     /// Represent the "ID" of the item, it will be used as the value of the `primary_key` when displayed in a [ListView]
     pub name: String,
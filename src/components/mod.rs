@@ -1,5 +1,110 @@
 //! Module containing all components to create UI according to Rtop style
+//!
+//! Every component returns exactly the requested `rows` x `cols` cells: shorter output is padded with spaces, longer output is clipped.
+//! Use [`check_size`] to verify an output in your tests, or [`set_size_assertions`] to make every component check its own output and panic on a violation.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::components::chart::Chart;
+//! use rtop_dev::components::{check_size, SizeError};
+//!
+//! let chart = Chart::new(10, 4, None, Some(true), None);
+//! assert_eq!(check_size(&chart.display(&[]), 4, 10), Ok(()));
+//!
+//! assert_eq!(
+//!     check_size("CPU\n", 2, 3),
+//!     Err(SizeError::Rows { expected: 2, actual: 1 })
+//! );
+//! ```
+
+use crate::markup;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 pub mod bar;
 pub mod chart;
 pub mod listview;
+
+/// Represent an output which doesn't take the requested number of cells
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SizeError {
+    /// The output doesn't have the requested number of lines
+    Rows { expected: usize, actual: usize },
+    /// A line of the output, starting at 1, doesn't take the requested number of cells
+    Cols {
+        line: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+impl std::fmt::Display for SizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rows { expected, actual } => {
+                write!(f, "expected {} lines, got {}", expected, actual)
+            }
+            Self::Cols {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {} takes {} cells instead of {}",
+                line, actual, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SizeError {}
+
+/// # Check that an output takes exactly `rows` x `cols` cells
+/// Negative sizes are handled as `0`. The width of the lines is their display width, without markers
+/// ## Arguments
+/// * `output` - The output of a widget or a component
+/// * `rows` - The expected number of lines
+/// * `cols` - The expected number of cells of every line
+pub fn check_size(output: &str, rows: i32, cols: i32) -> Result<(), SizeError> {
+    let (rows, cols) = (rows.max(0) as usize, cols.max(0) as usize);
+    let widths = markup::parse(output).widths();
+
+    if widths.len() != rows {
+        return Err(SizeError::Rows {
+            expected: rows,
+            actual: widths.len(),
+        });
+    }
+    match (1..).zip(widths).find(|(_, width)| *width != cols) {
+        Some((line, actual)) => Err(SizeError::Cols {
+            line,
+            expected: cols,
+            actual,
+        }),
+        None => Ok(()),
+    }
+}
+
+static SIZE_ASSERTIONS: AtomicBool = AtomicBool::new(false);
+
+/// # Enable or disable the size check of the components output
+/// Disabled by default. When enabled, a component returning an output of the wrong size panics with the reason.<br>
+/// **⚠️ Meant for tests: Rtop stops a widget which panics**
+/// ## Arguments
+/// * `enabled` - `true` to check every output
+pub fn set_size_assertions(enabled: bool) {
+    SIZE_ASSERTIONS.store(enabled, Ordering::Relaxed);
+}
+
+/// Panic if the size check is enabled and `output` doesn't take `rows` x `cols` cells
+fn assert_size(component: &str, output: &str, rows: i32, cols: i32) {
+    if !SIZE_ASSERTIONS.load(Ordering::Relaxed) {
+        return;
+    }
+    if let Err(err) = check_size(output, rows, cols) {
+        panic!(
+            "`{}` returned an output of the wrong size for {} x {} cells: {}",
+            component, rows, cols, err
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Module containing the error type shared by the whole crate
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::widget::Widget;
+//! use rtop_dev::Error;
+//! use std::time::Duration;
+//!
+//! struct LoadWidget {
+//!     load: Option<String>,
+//! }
+//!
+//! impl Widget for LoadWidget {
+//!     fn display(&mut self, height: i32, width: i32) -> String {
+//!         self.try_display(height, width)
+//!             .unwrap_or_else(|err| err.display(height, width))
+//!     }
+//!
+//!     fn try_display(&mut self, _height: i32, _width: i32) -> Result<String, Error> {
+//!         self.load
+//!             .clone()
+//!             .ok_or_else(|| Error::Unavailable(String::from("no data yet")))
+//!     }
+//!
+//!     fn try_update(&mut self, _elapsed: Duration) -> Result<(), Error> {
+//!         self.load = Some(std::fs::read_to_string("/proc/loadavg")?);
+//!         Ok(())
+//!     }
+//! }
+//!
+//! let mut widget = LoadWidget { load: None };
+//! assert!(widget.try_display(1, 40).is_err());
+//! ```
+
+use crate::ffi::AbiMismatch;
+use crate::guard::PanicError;
+use crate::manifest::ManifestError;
+use crate::style::{Color, Style};
+use crate::version::Incompatibility;
+
+/// Represent every error which can be returned by `rtop_dev` or by a [`Widget`](crate::widget::Widget)
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A data source used by the widget (file, socket, command...) is unavailable
+    Unavailable(String),
+    /// An I/O operation failed
+    Io(std::io::Error),
+    /// A widget callback panicked
+    Panic(PanicError),
+    /// The manifest of a plugin can't be read
+    Manifest(ManifestError),
+    /// A plugin is incompatible with the host
+    Incompatible(Incompatibility),
+    /// A plugin can't be loaded
+    #[cfg(feature = "host")]
+    Host(crate::host::HostError),
+    /// Any other error, described by its message
+    Other(String),
+}
+
+impl Error {
+    /// # Return the placeholder displayed in place of a widget which failed
+    /// ## Arguments
+    /// * `height` - The height of the placeholder
+    /// * `width` - The width of the placeholder
+    pub fn display(&self, height: i32, width: i32) -> String {
+        placeholder(&self.to_string(), height, width)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable(reason) => write!(f, "Data source unavailable: {}", reason),
+            Self::Io(err) => write!(f, "I/O error: {}", err),
+            Self::Panic(err) => err.fmt(f),
+            Self::Manifest(err) => err.fmt(f),
+            Self::Incompatible(err) => err.fmt(f),
+            #[cfg(feature = "host")]
+            Self::Host(err) => err.fmt(f),
+            Self::Other(reason) => f.write_str(reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Panic(err) => Some(err),
+            Self::Manifest(err) => Some(err),
+            Self::Incompatible(err) => Some(err),
+            #[cfg(feature = "host")]
+            Self::Host(err) => Some(err),
+            Self::Unavailable(_) | Self::Other(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<PanicError> for Error {
+    fn from(err: PanicError) -> Self {
+        Self::Panic(err)
+    }
+}
+
+impl From<ManifestError> for Error {
+    fn from(err: ManifestError) -> Self {
+        Self::Manifest(err)
+    }
+}
+
+impl From<Incompatibility> for Error {
+    fn from(err: Incompatibility) -> Self {
+        Self::Incompatible(err)
+    }
+}
+
+impl From<AbiMismatch> for Error {
+    fn from(err: AbiMismatch) -> Self {
+        Self::Incompatible(Incompatibility::Abi(err))
+    }
+}
+
+#[cfg(feature = "host")]
+impl From<crate::host::HostError> for Error {
+    fn from(err: crate::host::HostError) -> Self {
+        Self::Host(err)
+    }
+}
+
+/// Return `text` in red, wrapped and padded to take exactly `height` x `width` cells
+/// Each line of `text` starts a new row, and its control characters are replaced by a space
+pub(crate) fn placeholder(text: &str, height: i32, width: i32) -> String {
+    let (height, width) = (height.max(0) as usize, width.max(0) as usize);
+    let style = Style::new().fg(Color::Red).bg(Color::Black);
+
+    let mut lines = vec![];
+    for text in text.lines() {
+        let (mut line, mut line_width) = (String::new(), 0);
+        for symbol in crate::text::sanitize(text).chars() {
+            let symbol_width = crate::text::char_width(symbol);
+            if !line.is_empty() && line_width + symbol_width > width {
+                lines.push(std::mem::take(&mut line));
+                line_width = 0;
+            }
+            line.push(symbol);
+            line_width += symbol_width;
+        }
+        lines.push(line);
+    }
+    lines.retain(|line| !line.is_empty());
+    lines.resize(lines.len().max(height), String::new());
+
+    let mut out = String::new();
+    for line in lines.iter().take(height) {
+        if line.is_empty() {
+            out += &" ".repeat(width);
+        } else {
+            out += &style.paint(&crate::text::pad(line, width));
+        }
+        out.push('\n');
+    }
+    out
+}
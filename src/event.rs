@@ -0,0 +1,364 @@
+//! Module containing the events sent by Rtop to the widgets
+//!
+//! Rtop encodes the keys pressed by the user as strings, following the curses `keyname` format (`a`, `^C`, `M-x`, `KEY_UP`, `KEY_F(5)`, `kUP5`...).
+//! [`KeyEvent::parse`] turns them into a typed event, and its `Display` implementation does the opposite.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::event::{Key, KeyEvent, Modifiers};
+//!
+//! let event = KeyEvent::parse("^C");
+//! assert_eq!(event.key, Key::Char('c'));
+//! assert!(event.modifiers.ctrl);
+//!
+//! assert_eq!(KeyEvent::parse("KEY_UP"), KeyEvent::new(Key::Up, Modifiers::NONE));
+//! assert_eq!(KeyEvent::new(Key::Down, Modifiers::NONE).to_string(), "KEY_DOWN");
+//! ```
+//!
+//! Mouse events are sent as [`MouseEvent`], with coordinates relative to the top left corner of the widget.
+
+/// Represent a key pressed by the user, with the modifiers held at the same time
+/// Two events are equal if their key and modifiers are equal, whatever their `raw` value
+#[derive(Clone, Debug)]
+pub struct KeyEvent {
+    /// The pressed key
+    pub key: Key,
+    /// The modifiers held while pressing the key
+    pub modifiers: Modifiers,
+    /// The key as encoded by Rtop, `None` if the event wasn't parsed from a string.
+    /// Several strings represent the same key, such as `^J` and `KEY_ENTER`, this one is forwarded as is to [`Widget::on_input`](crate::widget::Widget::on_input)
+    pub raw: Option<String>,
+}
+
+impl KeyEvent {
+    /// # Create a new `KeyEvent`, without `raw` value
+    pub fn new(key: Key, modifiers: Modifiers) -> Self {
+        Self {
+            key,
+            modifiers,
+            raw: None,
+        }
+    }
+
+    /// # Parse a key encoded by Rtop
+    /// Strings which can't be recognized are returned as [`Key::Unknown`], the string is kept in `raw`
+    /// ## Arguments
+    /// * `value` - The key, as received by [`Widget::on_input`](crate::widget::Widget::on_input)
+    pub fn parse(value: &str) -> Self {
+        let mut event = Self::parse_key(value);
+        event.raw = Some(String::from(value));
+        event
+    }
+
+    fn parse_key(value: &str) -> Self {
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => return Self::from_char(c),
+            (Some('^'), Some(c)) if value.len() == 2 => return Self::from_control(c, value),
+            _ => {}
+        }
+
+        if let Some(key) = value.strip_prefix("M-") {
+            let mut event = Self::parse_key(key);
+            if !matches!(event.key, Key::Unknown(_)) {
+                event.modifiers.alt = true;
+                return event;
+            }
+        }
+
+        if let Some(number) = value
+            .strip_prefix("KEY_F(")
+            .and_then(|key| key.strip_suffix(')'))
+            .and_then(|number| number.parse::<u8>().ok())
+        {
+            return Self::new(Key::F(number), Modifiers::NONE);
+        }
+
+        if let Some((key, modifiers)) = value
+            .strip_prefix('k')
+            .and_then(|key| key.split_at_checked(key.len().saturating_sub(1)))
+        {
+            if let (Some(key), Some(modifiers)) = (extended_key(key), extended_modifiers(modifiers))
+            {
+                return Self::new(key, modifiers);
+            }
+        }
+
+        let shift = Modifiers {
+            shift: true,
+            ..Modifiers::NONE
+        };
+        let (key, modifiers) = match value {
+            "KEY_UP" => (Key::Up, Modifiers::NONE),
+            "KEY_DOWN" => (Key::Down, Modifiers::NONE),
+            "KEY_LEFT" => (Key::Left, Modifiers::NONE),
+            "KEY_RIGHT" => (Key::Right, Modifiers::NONE),
+            "KEY_HOME" => (Key::Home, Modifiers::NONE),
+            "KEY_END" => (Key::End, Modifiers::NONE),
+            "KEY_PPAGE" => (Key::PageUp, Modifiers::NONE),
+            "KEY_NPAGE" => (Key::PageDown, Modifiers::NONE),
+            "KEY_BACKSPACE" => (Key::Backspace, Modifiers::NONE),
+            "KEY_DC" => (Key::Delete, Modifiers::NONE),
+            "KEY_IC" => (Key::Insert, Modifiers::NONE),
+            "KEY_ENTER" => (Key::Enter, Modifiers::NONE),
+            "KEY_BTAB" => (Key::Tab, shift),
+            "KEY_SR" => (Key::Up, shift),
+            "KEY_SF" => (Key::Down, shift),
+            "KEY_SLEFT" => (Key::Left, shift),
+            "KEY_SRIGHT" => (Key::Right, shift),
+            "KEY_SHOME" => (Key::Home, shift),
+            "KEY_SEND" => (Key::End, shift),
+            "KEY_SDC" => (Key::Delete, shift),
+            _ => (Key::Unknown(String::from(value)), Modifiers::NONE),
+        };
+        Self::new(key, modifiers)
+    }
+
+    fn from_char(c: char) -> Self {
+        match c {
+            '\n' | '\r' => Self::new(Key::Enter, Modifiers::NONE),
+            '\t' => Self::new(Key::Tab, Modifiers::NONE),
+            '\u{1b}' => Self::new(Key::Escape, Modifiers::NONE),
+            '\u{7f}' | '\u{8}' => Self::new(Key::Backspace, Modifiers::NONE),
+            c if c.is_uppercase() => Self::new(
+                Key::Char(c),
+                Modifiers {
+                    shift: true,
+                    ..Modifiers::NONE
+                },
+            ),
+            c => Self::new(Key::Char(c), Modifiers::NONE),
+        }
+    }
+
+    fn from_control(c: char, value: &str) -> Self {
+        let key = match c {
+            'J' | 'M' => Key::Enter,
+            'I' => Key::Tab,
+            '[' => Key::Escape,
+            '?' | 'H' => Key::Backspace,
+            'A'..='Z' => {
+                return Self::new(
+                    Key::Char(c.to_ascii_lowercase()),
+                    Modifiers {
+                        ctrl: true,
+                        ..Modifiers::NONE
+                    },
+                )
+            }
+            _ => Key::Unknown(String::from(value)),
+        };
+        Self::new(key, Modifiers::NONE)
+    }
+}
+
+impl PartialEq for KeyEvent {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.modifiers == other.modifiers
+    }
+}
+
+impl Eq for KeyEvent {}
+
+impl std::hash::Hash for KeyEvent {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key.hash(state);
+        self.modifiers.hash(state);
+    }
+}
+
+impl From<&str> for KeyEvent {
+    fn from(value: &str) -> Self {
+        Self::parse(value)
+    }
+}
+
+impl std::fmt::Display for KeyEvent {
+    /// Encode the key the same way Rtop does, from its key and modifiers.
+    /// Some combinations, such as Ctrl+Enter, have no encoding and are written without their modifiers
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Modifiers { ctrl, alt, shift } = self.modifiers;
+
+        if let Some(name) = extended_name(&self.key) {
+            if ctrl || alt {
+                let code = 1 + u8::from(shift) + 2 * u8::from(alt) + 4 * u8::from(ctrl);
+                return write!(f, "k{}{}", name, code);
+            }
+        }
+
+        if alt {
+            f.write_str("M-")?;
+        }
+
+        match &self.key {
+            Key::Char(c) if ctrl && c.is_ascii_alphabetic() => {
+                write!(f, "^{}", c.to_ascii_uppercase())
+            }
+            Key::Char(c) => write!(f, "{}", c),
+            Key::Enter => f.write_str("^J"),
+            Key::Tab if shift => f.write_str("KEY_BTAB"),
+            Key::Tab => f.write_str("^I"),
+            Key::Escape => f.write_str("^["),
+            Key::Backspace => f.write_str("KEY_BACKSPACE"),
+            Key::Delete if shift => f.write_str("KEY_SDC"),
+            Key::Delete => f.write_str("KEY_DC"),
+            Key::Insert => f.write_str("KEY_IC"),
+            Key::Up if shift => f.write_str("KEY_SR"),
+            Key::Up => f.write_str("KEY_UP"),
+            Key::Down if shift => f.write_str("KEY_SF"),
+            Key::Down => f.write_str("KEY_DOWN"),
+            Key::Left if shift => f.write_str("KEY_SLEFT"),
+            Key::Left => f.write_str("KEY_LEFT"),
+            Key::Right if shift => f.write_str("KEY_SRIGHT"),
+            Key::Right => f.write_str("KEY_RIGHT"),
+            Key::Home if shift => f.write_str("KEY_SHOME"),
+            Key::Home => f.write_str("KEY_HOME"),
+            Key::End if shift => f.write_str("KEY_SEND"),
+            Key::End => f.write_str("KEY_END"),
+            Key::PageUp => f.write_str("KEY_PPAGE"),
+            Key::PageDown => f.write_str("KEY_NPAGE"),
+            Key::F(number) => write!(f, "KEY_F({})", number),
+            Key::Unknown(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Represent a key of the keyboard
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Key {
+    /// A printable character, uppercase letters are sent with the `shift` modifier
+    Char(char),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Insert,
+    Escape,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// A function key, from `F(1)` to `F(12)` on most keyboards
+    F(u8),
+    /// A key which can't be recognized, with its raw value
+    Unknown(String),
+}
+
+/// Represent an action of the mouse over a widget
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MouseEvent {
+    /// The action of the mouse
+    pub kind: MouseKind,
+    /// The column of the cursor, `0` being the left side of the widget
+    pub x: i32,
+    /// The row of the cursor, `0` being the first line of the widget output
+    pub y: i32,
+    /// The modifiers held during the action
+    pub modifiers: Modifiers,
+}
+
+impl MouseEvent {
+    /// # Create a new `MouseEvent` without modifiers
+    /// ## Arguments
+    /// * `kind` - The action of the mouse
+    /// * `x` - The column of the cursor, relative to the widget
+    /// * `y` - The row of the cursor, relative to the widget
+    pub fn new(kind: MouseKind, x: i32, y: i32) -> Self {
+        Self {
+            kind,
+            x,
+            y,
+            modifiers: Modifiers::NONE,
+        }
+    }
+}
+
+/// Represent the action of a [`MouseEvent`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MouseKind {
+    /// A button was pressed, this is what is usually called a click
+    Press(MouseButton),
+    /// A button was released
+    Release(MouseButton),
+    /// The cursor moved while the button was held
+    Drag(MouseButton),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Represent a button of the mouse
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+/// Represent the modifiers held while pressing a key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    /// The Control key
+    pub ctrl: bool,
+    /// The Alt (or Meta) key
+    pub alt: bool,
+    /// The Shift key
+    pub shift: bool,
+}
+
+impl Modifiers {
+    /// No modifier held
+    pub const NONE: Self = Self {
+        ctrl: false,
+        alt: false,
+        shift: false,
+    };
+}
+
+fn extended_name(key: &Key) -> Option<&'static str> {
+    match key {
+        Key::Up => Some("UP"),
+        Key::Down => Some("DN"),
+        Key::Left => Some("LFT"),
+        Key::Right => Some("RIT"),
+        Key::Home => Some("HOM"),
+        Key::End => Some("END"),
+        Key::Delete => Some("DC"),
+        Key::Insert => Some("IC"),
+        Key::PageUp => Some("PRV"),
+        Key::PageDown => Some("NXT"),
+        _ => None,
+    }
+}
+
+fn extended_key(name: &str) -> Option<Key> {
+    [
+        Key::Up,
+        Key::Down,
+        Key::Left,
+        Key::Right,
+        Key::Home,
+        Key::End,
+        Key::Delete,
+        Key::Insert,
+        Key::PageUp,
+        Key::PageDown,
+    ]
+    .into_iter()
+    .find(|key| extended_name(key) == Some(name))
+}
+
+fn extended_modifiers(code: &str) -> Option<Modifiers> {
+    let code = code.parse::<u8>().ok()?.checked_sub(1)?;
+    (1..8).contains(&code).then_some(Modifiers {
+        shift: code & 1 != 0,
+        alt: code & 2 != 0,
+        ctrl: code & 4 != 0,
+    })
+}
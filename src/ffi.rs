@@ -0,0 +1,1089 @@
+//! Module containing a stable C ABI layer around [`Widget`]
+//!
+//! Trait objects returned through `extern "Rust"` functions are only valid if the plugin and Rtop are built with the exact same compiler and `rtop_dev` version.
+//! This module exposes a `#[repr(C)]` vtable instead, so a plugin keep working when Rtop is upgraded, as long as the [`ABI_VERSION`] did not change.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::ffi::FfiWidget;
+//! use rtop_dev::widget::{Widget, WidgetDescriptor};
+//!
+//! struct FooWidget {}
+//!
+//! impl Widget for FooWidget {
+//!     fn display(&mut self, _height: i32, _width: i32) -> String {
+//!         String::from("Hello World RTop!")
+//!     }
+//! }
+//!
+//! // In the plugin
+//! #[no_mangle]
+//! pub extern "C" fn ffi_init_foo() -> FfiWidget {
+//!     FfiWidget::new(WidgetDescriptor::new(Box::new(FooWidget {}), "Foo"))
+//! }
+//!
+//! // In Rtop, `rtop_abi_version` is exported by the plugin and checked before calling `ffi_init_foo`
+//! assert_eq!(rtop_dev::ffi::rtop_abi_version(), rtop_dev::ffi::ABI_VERSION);
+//! let mut descriptor = ffi_init_foo().into_descriptor().unwrap();
+//! assert_eq!(descriptor.name, "Foo");
+//! assert_eq!(descriptor.widget.display(1, 17), "Hello World RTop!");
+//! ```
+
+use crate::event::{Key, KeyEvent, Modifiers, MouseButton, MouseEvent, MouseKind};
+use crate::frame::{Cell, Frame};
+use crate::guard::{GuardedWidget, PanicError};
+use crate::style::{self, Color};
+use crate::version::{Incompatibility, Requirement, Version};
+use crate::widget::{InputMode, Widget, WidgetDescriptor};
+use crate::Error;
+use std::ffi::c_void;
+use std::time::Duration;
+
+/// Version of the C ABI, incremented every time the layout of [`FfiWidget`] or of the existing functions of [`WidgetVTable`] change
+/// Adding a function at the end of the [`WidgetVTable`] doesn't change it
+pub const ABI_VERSION: u32 = 13;
+
+/// # Return the [`ABI_VERSION`] the library was built with
+/// Exported by every plugin linking `rtop_dev`. The layout of [`FfiWidget`] depends on the ABI version,
+/// so the host must call this function and compare its result before calling any `ffi_init_*` function
+#[no_mangle]
+pub extern "C" fn rtop_abi_version() -> u32 {
+    ABI_VERSION
+}
+
+/// Value returned by [`WidgetVTable::update_interval`] when the widget keeps its current interval
+pub const NO_INTERVAL: u64 = u64::MAX;
+
+/// Borrowed UTF-8 string or byte buffer, only valid for the duration of the call it's passed to
+#[repr(C)]
+pub struct FfiStr {
+    ptr: *const u8,
+    len: usize,
+}
+
+impl FfiStr {
+    /// # Borrow a `str` as a `FfiStr`
+    pub fn new(value: &str) -> Self {
+        Self {
+            ptr: value.as_ptr(),
+            len: value.len(),
+        }
+    }
+
+    /// # Borrow a byte buffer as a `FfiStr`
+    pub fn from_bytes(value: &[u8]) -> Self {
+        Self {
+            ptr: value.as_ptr(),
+            len: value.len(),
+        }
+    }
+
+    /// # Borrow the content of the `FfiStr` as bytes
+    /// ## Safety
+    /// The `FfiStr` must point to `len` readable bytes, which outlive `'a`
+    pub unsafe fn as_bytes<'a>(&self) -> &'a [u8] {
+        std::slice::from_raw_parts(self.ptr, self.len)
+    }
+
+    /// # Copy the content of the `FfiStr` into a `String`
+    /// ## Safety
+    /// The `FfiStr` must point to `len` readable bytes
+    pub unsafe fn to_owned_string(&self) -> String {
+        String::from_utf8_lossy(std::slice::from_raw_parts(self.ptr, self.len)).into_owned()
+    }
+
+    /// Borrow an optional `str`, `None` is represented by a null `ptr`
+    fn from_option(value: Option<&str>) -> Self {
+        value.map_or(
+            Self {
+                ptr: std::ptr::null(),
+                len: 0,
+            },
+            Self::new,
+        )
+    }
+
+    /// Copy the content of a `FfiStr` built by `from_option`
+    unsafe fn to_option(&self) -> Option<String> {
+        (!self.ptr.is_null()).then(|| self.to_owned_string())
+    }
+}
+
+/// Owned UTF-8 string or byte buffer, allocated by the plugin and released with [`WidgetVTable::free_string`]
+/// A null `ptr` represent the absence of value
+#[repr(C)]
+pub struct FfiString {
+    ptr: *mut u8,
+    len: usize,
+    capacity: usize,
+}
+
+impl FfiString {
+    fn none() -> Self {
+        Self {
+            ptr: std::ptr::null_mut(),
+            len: 0,
+            capacity: 0,
+        }
+    }
+
+    fn is_none(&self) -> bool {
+        self.ptr.is_null()
+    }
+}
+
+impl From<String> for FfiString {
+    fn from(value: String) -> Self {
+        Self::from(value.into_bytes())
+    }
+}
+
+impl From<Vec<u8>> for FfiString {
+    fn from(value: Vec<u8>) -> Self {
+        let mut bytes = std::mem::ManuallyDrop::new(value);
+        Self {
+            ptr: bytes.as_mut_ptr(),
+            len: bytes.len(),
+            capacity: bytes.capacity(),
+        }
+    }
+}
+
+/// Result of a fallible call, `value` contains the output on success and the error message on failure
+#[repr(C)]
+pub struct FfiResult {
+    status: u32,
+    value: FfiString,
+}
+
+impl FfiResult {
+    const OK: u32 = 0;
+    const UNAVAILABLE: u32 = 1;
+    const ERROR: u32 = 2;
+}
+
+impl From<Result<String, Error>> for FfiResult {
+    fn from(result: Result<String, Error>) -> Self {
+        match result {
+            Ok(value) => Self {
+                status: Self::OK,
+                value: FfiString::from(value),
+            },
+            Err(Error::Unavailable(reason)) => Self {
+                status: Self::UNAVAILABLE,
+                value: FfiString::from(reason),
+            },
+            Err(err) => Self {
+                status: Self::ERROR,
+                value: FfiString::from(err.to_string()),
+            },
+        }
+    }
+}
+
+/// FFI-safe version of a [`Cell`] of a [`Frame`]
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct FfiCell {
+    symbol: u32,
+    fg: u32,
+    bg: u32,
+    modifiers: u32,
+}
+
+impl From<Cell> for FfiCell {
+    fn from(cell: Cell) -> Self {
+        Self {
+            symbol: u32::from(cell.symbol),
+            fg: color_to_ffi(cell.fg),
+            bg: color_to_ffi(cell.bg),
+            modifiers: u32::from(cell.modifiers.bold)
+                | u32::from(cell.modifiers.italic) << 1
+                | u32::from(cell.modifiers.reverse) << 2
+                | u32::from(cell.modifiers.underline) << 3,
+        }
+    }
+}
+
+impl From<FfiCell> for Cell {
+    fn from(cell: FfiCell) -> Self {
+        Self {
+            symbol: char::from_u32(cell.symbol).unwrap_or(char::REPLACEMENT_CHARACTER),
+            fg: color_from_ffi(cell.fg),
+            bg: color_from_ffi(cell.bg),
+            modifiers: style::Modifiers {
+                bold: cell.modifiers & 1 != 0,
+                italic: cell.modifiers & 2 != 0,
+                reverse: cell.modifiers & 4 != 0,
+                underline: cell.modifiers & 8 != 0,
+            },
+        }
+    }
+}
+
+/// FFI-safe version of a [`MouseEvent`]
+#[repr(C)]
+pub struct FfiMouseEvent {
+    kind: u32,
+    button: u32,
+    x: i32,
+    y: i32,
+    modifiers: u32,
+}
+
+impl FfiMouseEvent {
+    fn into_event(self) -> Option<MouseEvent> {
+        let button = match self.button {
+            0 => MouseButton::Left,
+            1 => MouseButton::Right,
+            2 => MouseButton::Middle,
+            _ => return None,
+        };
+        let kind = match self.kind {
+            0 => MouseKind::Press(button),
+            1 => MouseKind::Release(button),
+            2 => MouseKind::Drag(button),
+            3 => MouseKind::ScrollUp,
+            4 => MouseKind::ScrollDown,
+            _ => return None,
+        };
+
+        Some(MouseEvent {
+            kind,
+            x: self.x,
+            y: self.y,
+            modifiers: modifiers_from_bits(self.modifiers),
+        })
+    }
+}
+
+impl From<MouseEvent> for FfiMouseEvent {
+    fn from(event: MouseEvent) -> Self {
+        let button = |button: MouseButton| match button {
+            MouseButton::Left => 0,
+            MouseButton::Right => 1,
+            MouseButton::Middle => 2,
+        };
+        let (kind, button) = match event.kind {
+            MouseKind::Press(pressed) => (0, button(pressed)),
+            MouseKind::Release(released) => (1, button(released)),
+            MouseKind::Drag(held) => (2, button(held)),
+            MouseKind::ScrollUp => (3, 0),
+            MouseKind::ScrollDown => (4, 0),
+        };
+
+        Self {
+            kind,
+            button,
+            x: event.x,
+            y: event.y,
+            modifiers: modifiers_to_bits(event.modifiers),
+        }
+    }
+}
+
+/// FFI-safe version of a [`KeyEvent`], only valid for the duration of the call it's passed to
+#[repr(C)]
+pub struct FfiKeyEvent {
+    key: u32,
+    /// The character of [`Key::Char`] or the number of [`Key::F`]
+    code: u32,
+    modifiers: u32,
+    /// The name of [`Key::Unknown`], null for other keys
+    name: FfiStr,
+    /// The [`KeyEvent::raw`] value, null for `None`
+    raw: FfiStr,
+}
+
+impl FfiKeyEvent {
+    /// # Borrow a [`KeyEvent`] as a `FfiKeyEvent`
+    pub fn new(event: &KeyEvent) -> Self {
+        let (key, code) = match event.key {
+            Key::Char(c) => (0, u32::from(c)),
+            Key::Enter => (1, 0),
+            Key::Tab => (2, 0),
+            Key::Backspace => (3, 0),
+            Key::Delete => (4, 0),
+            Key::Insert => (5, 0),
+            Key::Escape => (6, 0),
+            Key::Up => (7, 0),
+            Key::Down => (8, 0),
+            Key::Left => (9, 0),
+            Key::Right => (10, 0),
+            Key::Home => (11, 0),
+            Key::End => (12, 0),
+            Key::PageUp => (13, 0),
+            Key::PageDown => (14, 0),
+            Key::F(n) => (15, u32::from(n)),
+            Key::Unknown(_) => (16, 0),
+        };
+        let name = match &event.key {
+            Key::Unknown(name) => Some(name.as_str()),
+            _ => None,
+        };
+
+        Self {
+            key,
+            code,
+            modifiers: modifiers_to_bits(event.modifiers),
+            name: FfiStr::from_option(name),
+            raw: FfiStr::from_option(event.raw.as_deref()),
+        }
+    }
+
+    /// Keys added by a later version are returned as [`Key::Unknown`] with the raw value
+    unsafe fn into_event(self) -> KeyEvent {
+        let raw = self.raw.to_option();
+        let key = match (self.key, self.code) {
+            (0, code) => {
+                char::from_u32(code).map_or_else(|| Key::Unknown(String::new()), Key::Char)
+            }
+            (1, _) => Key::Enter,
+            (2, _) => Key::Tab,
+            (3, _) => Key::Backspace,
+            (4, _) => Key::Delete,
+            (5, _) => Key::Insert,
+            (6, _) => Key::Escape,
+            (7, _) => Key::Up,
+            (8, _) => Key::Down,
+            (9, _) => Key::Left,
+            (10, _) => Key::Right,
+            (11, _) => Key::Home,
+            (12, _) => Key::End,
+            (13, _) => Key::PageUp,
+            (14, _) => Key::PageDown,
+            (15, n) => Key::F(u8::try_from(n).unwrap_or(u8::MAX)),
+            _ => Key::Unknown(
+                self.name
+                    .to_option()
+                    .or_else(|| raw.clone())
+                    .unwrap_or_default(),
+            ),
+        };
+
+        KeyEvent {
+            key,
+            modifiers: modifiers_from_bits(self.modifiers),
+            raw,
+        }
+    }
+}
+
+fn modifiers_to_bits(modifiers: Modifiers) -> u32 {
+    u32::from(modifiers.shift) | u32::from(modifiers.alt) << 1 | u32::from(modifiers.ctrl) << 2
+}
+
+fn modifiers_from_bits(bits: u32) -> Modifiers {
+    Modifiers {
+        shift: bits & 1 != 0,
+        alt: bits & 2 != 0,
+        ctrl: bits & 4 != 0,
+    }
+}
+
+/// Table of `extern "C"` functions used to call the [`Widget`] methods across the library boundary
+/// The table is append-only: new functions are added at its end, without changing the [`ABI_VERSION`].
+/// The host reads `size` to know which functions the plugin was built with, and uses the default behavior of [`Widget`] for the missing ones.
+/// Only `free_string`, `drop` and `display` are required
+#[repr(C)]
+pub struct WidgetVTable {
+    /// Size of the table in bytes, in the version of `rtop_dev` the plugin was built with
+    pub size: usize,
+    /// Release a [`FfiString`] returned by the other functions
+    pub free_string: unsafe extern "C" fn(FfiString),
+    /// Destroy the widget
+    pub drop: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::try_display`]
+    pub display: unsafe extern "C" fn(*mut c_void, i32, i32) -> FfiResult,
+    /// Call [`Widget::render`] with a frame of `height` x `width` [`FfiCell`], allocated by the host
+    pub render: unsafe extern "C" fn(*mut c_void, i32, i32, *mut FfiCell) -> FfiResult,
+    /// Call [`Widget::needs_redraw`]
+    pub needs_redraw: unsafe extern "C" fn(*mut c_void) -> bool,
+    /// Call [`Widget::on_resize`]
+    pub on_resize: unsafe extern "C" fn(*mut c_void, i32, i32),
+    /// Call [`Widget::title`], a `None` title is represented by a null [`FfiString`]
+    pub title: unsafe extern "C" fn(*mut c_void) -> FfiString,
+    /// Call [`Widget::try_update`] with the elapsed time in microseconds
+    pub on_update: unsafe extern "C" fn(*mut c_void, u64) -> FfiResult,
+    /// Call [`Widget::update_interval`], return the interval in milliseconds or [`NO_INTERVAL`] for `None`
+    pub update_interval: unsafe extern "C" fn(*mut c_void) -> u64,
+    /// Call [`Widget::on_key`]
+    pub on_key: unsafe extern "C" fn(*mut c_void, FfiKeyEvent),
+    /// Call [`Widget::on_mouse`]
+    pub on_mouse: unsafe extern "C" fn(*mut c_void, FfiMouseEvent),
+    /// Call [`Widget::init`]
+    pub init: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::on_focus`]
+    pub on_focus: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::on_blur`]
+    pub on_blur: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::on_show`]
+    pub on_show: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::on_hide`]
+    pub on_hide: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::on_destroy`]
+    pub on_destroy: unsafe extern "C" fn(*mut c_void),
+    /// Call [`Widget::save_state`], a `None` state is represented by a null [`FfiString`]
+    pub save_state: unsafe extern "C" fn(*mut c_void) -> FfiString,
+    /// Call [`Widget::restore_state`]
+    pub restore_state: unsafe extern "C" fn(*mut c_void, FfiStr),
+}
+
+/// FFI-safe version of a [`WidgetDescriptor`], returned by the `ffi_init_*` functions of a plugin
+/// Its layout depends on the [`ABI_VERSION`], check [`rtop_abi_version`] before calling a `ffi_init_*` function.<br>
+/// **⚠️ A `FfiWidget` which is never converted with `into_descriptor` is leaked**
+#[repr(C)]
+pub struct FfiWidget {
+    abi_version: u32,
+    rtop_dev_version: [u32; 3],
+    min_host_version: [u32; 3],
+    max_host_version: [u32; 3],
+    data: *mut c_void,
+    vtable: *const WidgetVTable,
+    name: FfiString,
+    input: u32,
+    preferred_size: [i32; 2],
+    min_size: [i32; 2],
+    refresh_interval_ms: u64,
+}
+
+impl FfiWidget {
+    /// # Create a new `FfiWidget`, must be called from the plugin
+    /// The widget is wrapped in a [`GuardedWidget`], so its panics never unwind across the library boundary
+    /// ## Arguments
+    /// * `descriptor` - The descriptor of the widget to expose through the C ABI
+    pub fn new(descriptor: WidgetDescriptor) -> Self {
+        let data = Box::into_raw(Box::new(
+            Box::new(GuardedWidget::new(descriptor.widget)) as Box<dyn Widget>
+        ))
+        .cast::<c_void>();
+        Self {
+            input: match descriptor.input {
+                InputMode::None => 0,
+                InputMode::Keyboard => 1,
+                InputMode::Mouse => 2,
+                InputMode::KeyboardAndMouse => 3,
+            },
+            preferred_size: size_to_ffi(descriptor.preferred_size),
+            min_size: size_to_ffi(descriptor.min_size),
+            refresh_interval_ms: descriptor.refresh_interval.as_millis() as u64,
+            ..Self::with_data(data, FfiString::from(descriptor.name))
+        }
+    }
+
+    /// # Create the `FfiWidget` of the descriptor returned by `init`, must be called from the plugin
+    /// A panic of `init` doesn't unwind across the library boundary: it is reported by [`into_descriptor`](Self::into_descriptor) instead
+    /// ## Arguments
+    /// * `init` - The function creating the descriptor of the widget, see [`export_widget!`](crate::export_widget)
+    pub fn from_init(init: fn() -> WidgetDescriptor) -> Self {
+        match std::panic::catch_unwind(init) {
+            Ok(descriptor) => Self::new(descriptor),
+            Err(payload) => {
+                // A null `data` tells `into_descriptor` that `name` holds the panic message
+                let error = PanicError::new("new", &*payload);
+                Self::with_data(std::ptr::null_mut(), FfiString::from(error.message))
+            }
+        }
+    }
+
+    /// Create a `FfiWidget` with the versions of this crate, without input, size nor refresh interval
+    fn with_data(data: *mut c_void, name: FfiString) -> Self {
+        let requirement = Requirement::compatible_with(Version::current());
+        Self {
+            abi_version: ABI_VERSION,
+            rtop_dev_version: version_to_ffi(Version::current()),
+            min_host_version: version_to_ffi(requirement.min),
+            max_host_version: version_to_ffi(requirement.max),
+            data,
+            vtable: &VTABLE,
+            name,
+            input: 0,
+            preferred_size: size_to_ffi(None),
+            min_size: size_to_ffi(None),
+            refresh_interval_ms: 0,
+        }
+    }
+
+    /// # Return the ABI version the widget was built with
+    pub fn abi_version(&self) -> u32 {
+        self.abi_version
+    }
+
+    /// # Return the version of `rtop_dev` the widget was built with
+    /// **⚠️ Only meaningful if the ABI version is the same as the host one**
+    pub fn rtop_dev_version(&self) -> Version {
+        version_from_ffi(self.rtop_dev_version)
+    }
+
+    /// # Check that the widget can be used by the host
+    /// Compare the ABI version and check the vtable has the required functions,
+    /// then check the host version is in the range supported by the widget, see [`version::check`](crate::version::check)
+    pub fn check(&self) -> Result<(), Incompatibility> {
+        if self.abi_version != ABI_VERSION {
+            return Err(Incompatibility::Abi(AbiMismatch {
+                expected: ABI_VERSION,
+                found: self.abi_version,
+            }));
+        }
+        // SAFETY: the vtable is a static of the plugin, and `size` is its first field in every ABI version
+        let vtable_size = unsafe { (*self.vtable).size };
+        if vtable_size < REQUIRED_VTABLE_SIZE {
+            return Err(Incompatibility::InvalidVTable(vtable_size));
+        }
+
+        crate::version::check(
+            self.rtop_dev_version(),
+            &Requirement {
+                min: version_from_ffi(self.min_host_version),
+                max: version_from_ffi(self.max_host_version),
+            },
+        )
+    }
+
+    /// # Check the widget compatibility and return the [`WidgetDescriptor`], the widget is a [`ForeignWidget`]
+    /// Return [`Error::Incompatible`] if the widget can't be used by the host, or [`Error::Panic`] if its creation panicked
+    pub fn into_descriptor(mut self) -> Result<WidgetDescriptor, Error> {
+        self.check().map_err(Error::Incompatible)?;
+
+        let name = std::mem::replace(&mut self.name, FfiString::none());
+        if self.data.is_null() {
+            let message = self.take_string(name).unwrap_or_default();
+            return Err(Error::Panic(PanicError {
+                callback: "new",
+                message,
+            }));
+        }
+        let input = match self.input {
+            1 => InputMode::Keyboard,
+            2 => InputMode::Mouse,
+            3 => InputMode::KeyboardAndMouse,
+            _ => InputMode::None,
+        };
+        let preferred_size = size_from_ffi(self.preferred_size);
+        let min_size = size_from_ffi(self.min_size);
+        let refresh_interval = Duration::from_millis(self.refresh_interval_ms);
+
+        let name = self.take_string(name).unwrap_or_default();
+        let widget = ForeignWidget { inner: self };
+
+        let mut descriptor = WidgetDescriptor::new(Box::new(widget), &name);
+        descriptor.input = input;
+        descriptor.preferred_size = preferred_size;
+        descriptor.min_size = min_size;
+        descriptor.refresh_interval = refresh_interval;
+
+        Ok(descriptor)
+    }
+}
+
+/// Size of the functions every [`WidgetVTable`] must have, up to `display`
+const REQUIRED_VTABLE_SIZE: usize =
+    std::mem::offset_of!(WidgetVTable, display) + std::mem::size_of::<unsafe extern "C" fn()>();
+
+/// Read a function of the [`WidgetVTable`] of a [`FfiWidget`], `None` if the plugin was built before the function was added
+macro_rules! slot {
+    ($widget:expr, $field:ident) => {{
+        let vtable: *const WidgetVTable = $widget.vtable;
+        let end = std::mem::offset_of!(WidgetVTable, $field)
+            + std::mem::size_of::<unsafe extern "C" fn()>();
+        // SAFETY: the vtable is a static of the plugin of at least `size` bytes,
+        // the function is read through a raw pointer, without borrowing the whole table
+        unsafe { ((*vtable).size >= end).then(|| std::ptr::addr_of!((*vtable).$field).read()) }
+    }};
+}
+
+impl FfiWidget {
+    /// Copy a string returned by the plugin and release it with the plugin allocator
+    fn take_string(&self, value: FfiString) -> Option<String> {
+        self.take_bytes(value)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    fn take_bytes(&self, value: FfiString) -> Option<Vec<u8>> {
+        if value.is_none() {
+            return None;
+        }
+        // SAFETY: the buffer was created by `FfiString::from` in the plugin
+        let out = unsafe { std::slice::from_raw_parts(value.ptr, value.len).to_vec() };
+        if let Some(free_string) = slot!(self, free_string) {
+            unsafe { free_string(value) };
+        }
+        Some(out)
+    }
+}
+
+/// Host side of a [`FfiWidget`], forward every [`Widget`] call to the plugin through the [`WidgetVTable`]
+/// The functions missing from the vtable of an older plugin behave like the default implementation of [`Widget`]
+pub struct ForeignWidget {
+    inner: FfiWidget,
+}
+
+impl ForeignWidget {
+    fn take_string(&self, value: FfiString) -> Option<String> {
+        self.inner.take_string(value)
+    }
+
+    fn take_bytes(&self, value: FfiString) -> Option<Vec<u8>> {
+        self.inner.take_bytes(value)
+    }
+
+    fn take_result(&self, result: FfiResult) -> Result<String, Error> {
+        let value = self.take_string(result.value).unwrap_or_default();
+        match result.status {
+            FfiResult::OK => Ok(value),
+            FfiResult::UNAVAILABLE => Err(Error::Unavailable(value)),
+            _ => Err(Error::Other(value)),
+        }
+    }
+}
+
+impl Widget for ForeignWidget {
+    fn display(&mut self, height: i32, width: i32) -> String {
+        self.try_display(height, width)
+            .unwrap_or_else(|err| err.display(height, width))
+    }
+
+    fn try_display(&mut self, height: i32, width: i32) -> Result<String, Error> {
+        let Some(display) = slot!(self.inner, display) else {
+            return Err(Error::Other(String::from("the widget can't be displayed")));
+        };
+        let out = unsafe { display(self.inner.data, height, width) };
+        self.take_result(out)
+    }
+
+    fn render(&mut self, frame: &mut Frame) -> Result<(), Error> {
+        let Some(render) = slot!(self.inner, render) else {
+            let output = self.try_display(frame.height(), frame.width())?;
+            frame.print_markup(0, 0, &output);
+            return Ok(());
+        };
+        let mut cells = frame
+            .cells()
+            .iter()
+            .map(|cell| FfiCell::from(*cell))
+            .collect::<Vec<FfiCell>>();
+        let out = unsafe {
+            render(
+                self.inner.data,
+                frame.height(),
+                frame.width(),
+                cells.as_mut_ptr(),
+            )
+        };
+        self.take_result(out)?;
+
+        for (cell, ffi_cell) in frame.cells_mut().iter_mut().zip(cells) {
+            *cell = Cell::from(ffi_cell);
+        }
+        Ok(())
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        slot!(self.inner, needs_redraw)
+            .is_none_or(|needs_redraw| unsafe { needs_redraw(self.inner.data) })
+    }
+
+    fn on_resize(&mut self, height: i32, width: i32) {
+        if let Some(on_resize) = slot!(self.inner, on_resize) {
+            unsafe { on_resize(self.inner.data, height, width) };
+        }
+    }
+
+    fn title(&mut self) -> Option<String> {
+        let title = slot!(self.inner, title)?;
+        let out = unsafe { title(self.inner.data) };
+        self.take_string(out)
+    }
+
+    fn on_update(&mut self, elapsed: Duration) {
+        self.try_update(elapsed).ok();
+    }
+
+    fn try_update(&mut self, elapsed: Duration) -> Result<(), Error> {
+        let Some(on_update) = slot!(self.inner, on_update) else {
+            return Ok(());
+        };
+        let elapsed = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        let out = unsafe { on_update(self.inner.data, elapsed) };
+        self.take_result(out).map(|_| ())
+    }
+
+    fn update_interval(&mut self) -> Option<Duration> {
+        let update_interval = slot!(self.inner, update_interval)?;
+        let out = unsafe { update_interval(self.inner.data) };
+        (out != NO_INTERVAL).then(|| Duration::from_millis(out))
+    }
+
+    fn on_input(&mut self, key: String) {
+        self.on_key(KeyEvent::parse(&key));
+    }
+
+    fn on_key(&mut self, key: KeyEvent) {
+        if let Some(on_key) = slot!(self.inner, on_key) {
+            unsafe { on_key(self.inner.data, FfiKeyEvent::new(&key)) };
+        }
+    }
+
+    fn on_mouse(&mut self, event: MouseEvent) {
+        if let Some(on_mouse) = slot!(self.inner, on_mouse) {
+            unsafe { on_mouse(self.inner.data, FfiMouseEvent::from(event)) };
+        }
+    }
+
+    fn init(&mut self) {
+        if let Some(init) = slot!(self.inner, init) {
+            unsafe { init(self.inner.data) };
+        }
+    }
+
+    fn on_focus(&mut self) {
+        if let Some(on_focus) = slot!(self.inner, on_focus) {
+            unsafe { on_focus(self.inner.data) };
+        }
+    }
+
+    fn on_blur(&mut self) {
+        if let Some(on_blur) = slot!(self.inner, on_blur) {
+            unsafe { on_blur(self.inner.data) };
+        }
+    }
+
+    fn on_show(&mut self) {
+        if let Some(on_show) = slot!(self.inner, on_show) {
+            unsafe { on_show(self.inner.data) };
+        }
+    }
+
+    fn on_hide(&mut self) {
+        if let Some(on_hide) = slot!(self.inner, on_hide) {
+            unsafe { on_hide(self.inner.data) };
+        }
+    }
+
+    fn on_destroy(&mut self) {
+        if let Some(on_destroy) = slot!(self.inner, on_destroy) {
+            unsafe { on_destroy(self.inner.data) };
+        }
+    }
+
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        let save_state = slot!(self.inner, save_state)?;
+        let out = unsafe { save_state(self.inner.data) };
+        self.take_bytes(out)
+    }
+
+    fn restore_state(&mut self, state: &[u8]) {
+        if let Some(restore_state) = slot!(self.inner, restore_state) {
+            unsafe { restore_state(self.inner.data, FfiStr::from_bytes(state)) };
+        }
+    }
+}
+
+impl Drop for ForeignWidget {
+    fn drop(&mut self) {
+        if let Some(drop_widget) = slot!(self.inner, drop) {
+            unsafe { drop_widget(self.inner.data) };
+        }
+    }
+}
+
+/// Error returned when a [`FfiWidget`] was built with an incompatible [`ABI_VERSION`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiMismatch {
+    /// The ABI version of the host
+    pub expected: u32,
+    /// The ABI version of the plugin
+    pub found: u32,
+}
+
+impl std::fmt::Display for AbiMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "incompatible widget ABI version: expected {}, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl std::error::Error for AbiMismatch {}
+
+fn version_to_ffi(version: Version) -> [u32; 3] {
+    [version.major, version.minor, version.patch]
+}
+
+fn version_from_ffi(version: [u32; 3]) -> Version {
+    Version::new(version[0], version[1], version[2])
+}
+
+fn color_to_ffi(color: Color) -> u32 {
+    match color {
+        Color::Black => 1,
+        Color::Red => 2,
+        Color::Green => 3,
+        Color::Yellow => 4,
+        Color::Blue => 5,
+        Color::Magenta => 6,
+        Color::Cyan => 7,
+        Color::White => 8,
+        Color::Default => 0,
+    }
+}
+
+fn color_from_ffi(color: u32) -> Color {
+    match color {
+        1 => Color::Black,
+        2 => Color::Red,
+        3 => Color::Green,
+        4 => Color::Yellow,
+        5 => Color::Blue,
+        6 => Color::Magenta,
+        7 => Color::Cyan,
+        8 => Color::White,
+        _ => Color::Default,
+    }
+}
+
+fn size_to_ffi(size: Option<(i32, i32)>) -> [i32; 2] {
+    size.map_or([0, 0], |(height, width)| [height, width])
+}
+
+fn size_from_ffi(size: [i32; 2]) -> Option<(i32, i32)> {
+    (size != [0, 0]).then_some((size[0], size[1]))
+}
+
+static VTABLE: WidgetVTable = WidgetVTable {
+    size: std::mem::size_of::<WidgetVTable>(),
+    free_string,
+    drop: widget_drop,
+    display: widget_display,
+    render: widget_render,
+    needs_redraw: widget_needs_redraw,
+    on_resize: widget_on_resize,
+    title: widget_title,
+    on_update: widget_on_update,
+    update_interval: widget_update_interval,
+    on_key: widget_on_key,
+    on_mouse: widget_on_mouse,
+    init: widget_init,
+    on_focus: widget_on_focus,
+    on_blur: widget_on_blur,
+    on_show: widget_on_show,
+    on_hide: widget_on_hide,
+    on_destroy: widget_on_destroy,
+    save_state: widget_save_state,
+    restore_state: widget_restore_state,
+};
+
+unsafe fn widget<'a>(data: *mut c_void) -> &'a mut Box<dyn Widget> {
+    &mut *data.cast::<Box<dyn Widget>>()
+}
+
+unsafe extern "C" fn widget_display(data: *mut c_void, height: i32, width: i32) -> FfiResult {
+    FfiResult::from(widget(data).try_display(height, width))
+}
+
+unsafe extern "C" fn widget_render(
+    data: *mut c_void,
+    height: i32,
+    width: i32,
+    cells: *mut FfiCell,
+) -> FfiResult {
+    let mut frame = Frame::new(height, width);
+    let cells = std::slice::from_raw_parts_mut(cells, frame.cells().len());
+    for (cell, ffi_cell) in frame.cells_mut().iter_mut().zip(cells.iter()) {
+        *cell = Cell::from(*ffi_cell);
+    }
+
+    let result = widget(data).render(&mut frame);
+    if result.is_ok() {
+        for (ffi_cell, cell) in cells.iter_mut().zip(frame.cells()) {
+            *ffi_cell = FfiCell::from(*cell);
+        }
+    }
+    FfiResult::from(result.map(|_| String::new()))
+}
+
+unsafe extern "C" fn widget_needs_redraw(data: *mut c_void) -> bool {
+    widget(data).needs_redraw()
+}
+
+unsafe extern "C" fn widget_on_resize(data: *mut c_void, height: i32, width: i32) {
+    widget(data).on_resize(height, width);
+}
+
+unsafe extern "C" fn widget_title(data: *mut c_void) -> FfiString {
+    widget(data)
+        .title()
+        .map_or_else(FfiString::none, FfiString::from)
+}
+
+unsafe extern "C" fn widget_on_update(data: *mut c_void, elapsed_us: u64) -> FfiResult {
+    FfiResult::from(
+        widget(data)
+            .try_update(Duration::from_micros(elapsed_us))
+            .map(|_| String::new()),
+    )
+}
+
+unsafe extern "C" fn widget_update_interval(data: *mut c_void) -> u64 {
+    widget(data)
+        .update_interval()
+        .map_or(NO_INTERVAL, |interval| {
+            u64::try_from(interval.as_millis())
+                .unwrap_or(u64::MAX)
+                .min(NO_INTERVAL - 1)
+        })
+}
+
+unsafe extern "C" fn widget_on_key(data: *mut c_void, key: FfiKeyEvent) {
+    widget(data).on_key(key.into_event());
+}
+
+unsafe extern "C" fn widget_on_mouse(data: *mut c_void, event: FfiMouseEvent) {
+    if let Some(event) = event.into_event() {
+        widget(data).on_mouse(event);
+    }
+}
+
+unsafe extern "C" fn widget_init(data: *mut c_void) {
+    widget(data).init();
+}
+
+unsafe extern "C" fn widget_on_focus(data: *mut c_void) {
+    widget(data).on_focus();
+}
+
+unsafe extern "C" fn widget_on_blur(data: *mut c_void) {
+    widget(data).on_blur();
+}
+
+unsafe extern "C" fn widget_on_show(data: *mut c_void) {
+    widget(data).on_show();
+}
+
+unsafe extern "C" fn widget_on_hide(data: *mut c_void) {
+    widget(data).on_hide();
+}
+
+unsafe extern "C" fn widget_on_destroy(data: *mut c_void) {
+    widget(data).on_destroy();
+}
+
+unsafe extern "C" fn widget_save_state(data: *mut c_void) -> FfiString {
+    widget(data)
+        .save_state()
+        .map_or_else(FfiString::none, FfiString::from)
+}
+
+unsafe extern "C" fn widget_restore_state(data: *mut c_void, state: FfiStr) {
+    widget(data).restore_state(state.as_bytes());
+}
+
+unsafe extern "C" fn free_string(value: FfiString) {
+    if !value.is_none() {
+        drop(Vec::from_raw_parts(value.ptr, value.len, value.capacity));
+    }
+}
+
+unsafe extern "C" fn widget_drop(data: *mut c_void) {
+    drop(Box::from_raw(data.cast::<Box<dyn Widget>>()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_events_keep_their_modifiers_across_the_boundary() {
+        let events = [
+            KeyEvent::new(
+                Key::Enter,
+                Modifiers {
+                    ctrl: true,
+                    ..Modifiers::NONE
+                },
+            ),
+            KeyEvent::new(
+                Key::F(5),
+                Modifiers {
+                    shift: true,
+                    ..Modifiers::NONE
+                },
+            ),
+            KeyEvent::new(
+                Key::Char('1'),
+                Modifiers {
+                    ctrl: true,
+                    ..Modifiers::NONE
+                },
+            ),
+            KeyEvent::new(Key::Unknown(String::from("KEY_MOUSE")), Modifiers::NONE),
+            KeyEvent::parse("KEY_ENTER"),
+            KeyEvent::parse("^?"),
+        ];
+
+        for event in events {
+            let received = unsafe { FfiKeyEvent::new(&event).into_event() };
+            assert_eq!(received, event);
+            assert_eq!(received.raw, event.raw);
+        }
+    }
+
+    struct Old {}
+
+    impl Widget for Old {
+        fn display(&mut self, _height: i32, _width: i32) -> String {
+            String::from("old")
+        }
+
+        fn needs_redraw(&mut self) -> bool {
+            false
+        }
+
+        fn title(&mut self) -> Option<String> {
+            Some(String::from("Old"))
+        }
+    }
+
+    /// Return a `FfiWidget` whose vtable stops after `display`, as built by a plugin before the other functions were added
+    fn old_widget(vtable_size: usize) -> FfiWidget {
+        let vtable = Box::leak(Box::new(WidgetVTable {
+            size: vtable_size,
+            ..VTABLE
+        }));
+        FfiWidget {
+            vtable,
+            ..FfiWidget::new(WidgetDescriptor::new(Box::new(Old {}), "Old"))
+        }
+    }
+
+    #[test]
+    fn missing_functions_behave_like_the_default_implementation() {
+        let mut widget = old_widget(REQUIRED_VTABLE_SIZE)
+            .into_descriptor()
+            .unwrap()
+            .widget;
+
+        assert_eq!(widget.display(1, 5), "old");
+        assert!(widget.needs_redraw());
+        assert_eq!(widget.title(), None);
+        assert_eq!(widget.update_interval(), None);
+        assert_eq!(widget.save_state(), None);
+        assert!(widget.try_update(Duration::from_secs(1)).is_ok());
+        widget.on_key(KeyEvent::parse("a"));
+
+        let mut frame = Frame::new(1, 5);
+        widget.render(&mut frame).unwrap();
+        assert_eq!(frame.to_markup(), "old  \n");
+    }
+
+    #[test]
+    fn vtables_without_the_required_functions_are_rejected() {
+        let size = std::mem::offset_of!(WidgetVTable, display);
+        assert_eq!(
+            old_widget(size).check(),
+            Err(Incompatibility::InvalidVTable(size))
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Module containing a grid of styled cells, which widgets and components can render into
+//!
+//! Rtop historically receives the output of a widget as a `String` with inline `[[EFFECT_*]]` markers.
+//! A [`Frame`] holds the same output as typed cells, and can be converted from and to this format.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::components::bar::{Color, Horizontal};
+//! use rtop_dev::frame::Frame;
+//! use rtop_dev::style::{self, Style};
+//!
+//! let mut frame = Frame::new(2, 10);
+//! frame.print(0, 0, "CPU", Style::NONE);
+//! Horizontal::new(1, 10, Some(Color::Blue)).render(50.0, &mut frame, 1, 0);
+//!
+//! assert_eq!(frame.get(1, 0).unwrap().symbol, '█');
+//! assert_eq!(frame.get(1, 0).unwrap().fg, style::Color::Blue);
+//!
+//! let output = frame.to_markup(); // Legacy format, as returned by `Widget::display`
+//! ```
+
+use crate::markup;
+use crate::style::{Color, Modifiers, Style};
+use crate::text;
+
+/// Represent a cell of a [`Frame`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cell {
+    /// The character displayed in the cell, `'\0'` for the cell covered by the wide character on its left
+    pub symbol: char,
+    /// The color of the character
+    pub fg: Color,
+    /// The color of the background
+    pub bg: Color,
+    /// The effects applied to the character
+    pub modifiers: Modifiers,
+}
+
+impl Cell {
+    /// A blank cell, without any style
+    pub const EMPTY: Self = Self::new(' ', Style::NONE);
+
+    /// # Create a new `Cell`
+    /// ## Arguments
+    /// * `symbol` - The character displayed in the cell
+    /// * `style` - The colors and effects of the cell
+    pub const fn new(symbol: char, style: Style) -> Self {
+        Self {
+            symbol,
+            fg: style.fg,
+            bg: style.bg,
+            modifiers: style.modifiers,
+        }
+    }
+
+    /// # Return the colors and effects of the cell
+    pub fn style(&self) -> Style {
+        Style {
+            fg: self.fg,
+            bg: self.bg,
+            modifiers: self.modifiers,
+        }
+    }
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// Grid of `height` x `width` styled cells
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    height: i32,
+    width: i32,
+    cells: Vec<Cell>,
+}
+
+impl Frame {
+    /// # Create a new `Frame` filled with blank cells
+    /// ## Arguments
+    /// * `height` - The number of rows of the frame
+    /// * `width` - The number of columns of the frame
+    pub fn new(height: i32, width: i32) -> Self {
+        let height = height.max(0);
+        let width = width.max(0);
+
+        Self {
+            height,
+            width,
+            cells: vec![Cell::EMPTY; (height * width) as usize],
+        }
+    }
+
+    /// # Create a new `Frame` from a string using the `[[EFFECT_*]]` markers
+    /// Text outside of the frame is clipped
+    /// ## Arguments
+    /// * `markup` - The output of a widget or a component
+    /// * `height` - The number of rows of the frame
+    /// * `width` - The number of columns of the frame
+    pub fn from_markup(markup: &str, height: i32, width: i32) -> Self {
+        let mut frame = Self::new(height, width);
+        frame.print_markup(0, 0, markup);
+        frame
+    }
+
+    /// # Return the number of rows of the frame
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// # Return the number of columns of the frame
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    /// # Return all the cells of the frame, row by row
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// # Return all the cells of the frame, row by row
+    pub fn cells_mut(&mut self) -> &mut [Cell] {
+        &mut self.cells
+    }
+
+    /// # Return the cells of every row of the frame
+    pub fn rows(&self) -> impl Iterator<Item = &[Cell]> {
+        let width = self.width as usize;
+        (0..self.height as usize).map(move |y| &self.cells[y * width..(y + 1) * width])
+    }
+
+    /// # Return the cell at the given position, `None` if it's outside of the frame
+    pub fn get(&self, row: i32, col: i32) -> Option<&Cell> {
+        self.index(row, col).map(|index| &self.cells[index])
+    }
+
+    /// # Return the cell at the given position, `None` if it's outside of the frame
+    pub fn get_mut(&mut self, row: i32, col: i32) -> Option<&mut Cell> {
+        self.index(row, col).map(|index| &mut self.cells[index])
+    }
+
+    /// # Reset every cell of the frame to a blank cell
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::EMPTY);
+    }
+
+    /// # Write a single line of text, starting at the given position
+    /// The text is clipped at the end of the row, and its control characters are replaced by a space, see [`text::sanitize`].
+    /// A wide character takes two cells, and is replaced by a space if only one is left.<br>
+    /// **⚠️ Zero-width characters, such as combining marks, aren't stored in the frame**
+    /// ## Arguments
+    /// * `row` - The row of the first character
+    /// * `col` - The column of the first character
+    /// * `text` - The text to write, without markers
+    /// * `style` - The style of the written cells
+    pub fn print(&mut self, row: i32, col: i32, text: &str, style: Style) {
+        let mut col = col;
+        for symbol in text::sanitize(text).chars() {
+            match text::char_width(symbol) {
+                0 => continue,
+                1 => self.set(row, col, Cell::new(symbol, style)),
+                _ if col + 1 == self.width => self.set(row, col, Cell::new(' ', style)),
+                _ => {
+                    self.set(row, col, Cell::new(symbol, style));
+                    self.set(row, col + 1, Cell::new('\0', style));
+                    col += 1;
+                }
+            }
+            col += 1;
+        }
+    }
+
+    /// # Write a string using the `[[EFFECT_*]]` markers, starting at the given position
+    /// Each line of the string is written on the next row, from the same column. Unknown markers are written as text
+    /// ## Arguments
+    /// * `row` - The row of the first line
+    /// * `col` - The column of the start of every line
+    /// * `markup` - The output of a widget or a component
+    pub fn print_markup(&mut self, row: i32, col: i32, markup: &str) {
+        for (y, line) in (row..).zip(markup::parse(markup).lines) {
+            let mut x = col;
+            for span in &line.spans {
+                self.print(y, x, &span.content, span.style);
+                x += span.width() as i32;
+            }
+        }
+    }
+
+    /// # Return the content of the frame as a string using the `[[EFFECT_*]]` markers
+    /// This is the format returned by [`Widget::display`](crate::widget::Widget::display), every row ends with a line break.<br>
+    /// **⚠️ Rtop only supports colors by pair: a cell with a single [`Color::Default`] is written with `WHITE` or `BLACK` instead, see [`Style::markers`],
+    /// so `from_markup` only gives back the same frame if every cell has either both or none of its colors set**
+    pub fn to_markup(&self) -> String {
+        let mut out = String::new();
+        for row in self.rows() {
+            let mut start = 0;
+            while start < row.len() {
+                let style = row[start].style();
+                let len = row[start..]
+                    .iter()
+                    .take_while(|cell| cell.style() == style)
+                    .count();
+
+                let text = row[start..start + len]
+                    .iter()
+                    .map(|cell| cell.symbol)
+                    .filter(|symbol| *symbol != '\0')
+                    .collect::<String>();
+                out += &style.paint(&text);
+                start += len;
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn set(&mut self, row: i32, col: i32, cell: Cell) {
+        if let Some(current) = self.get_mut(row, col) {
+            *current = cell;
+        }
+    }
+
+    fn index(&self, row: i32, col: i32) -> Option<usize> {
+        ((0..self.height).contains(&row) && (0..self.width).contains(&col))
+            .then_some((row * self.width + col) as usize)
+    }
+}
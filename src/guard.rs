@@ -0,0 +1,225 @@
+//! Module containing a [`Widget`] wrapper isolating Rtop from the panics of a plugin
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::guard::GuardedWidget;
+//! use rtop_dev::widget::Widget;
+//!
+//! struct FooWidget {}
+//!
+//! impl Widget for FooWidget {
+//!     fn display(&mut self, _height: i32, _width: i32) -> String {
+//!         panic!("Oops");
+//!     }
+//! }
+//!
+//! let mut widget = GuardedWidget::new(Box::new(FooWidget {}));
+//! let output = widget.display(1, 40); // Display an error placeholder instead of panicking
+//!
+//! assert_eq!(widget.error().unwrap().message, "Oops");
+//! ```
+
+use crate::event::{KeyEvent, MouseEvent};
+use crate::frame::Frame;
+use crate::widget::Widget;
+use crate::Error;
+use std::time::Duration;
+
+/// Wrapper around a [`Widget`] catching the panics of its callbacks
+/// Once a callback panicked, the widget is considered broken: its callbacks are no longer called and an error placeholder is displayed instead.
+/// Only `on_destroy` is still called, so the widget can release its resources.<br>
+/// **⚠️ Panics can only be caught if the plugin is not built with `panic = "abort"`**
+pub struct GuardedWidget {
+    widget: Box<dyn Widget>,
+    error: Option<PanicError>,
+}
+
+impl GuardedWidget {
+    /// # Create a new `GuardedWidget`
+    /// ## Arguments
+    /// * `widget` - The widget to protect
+    pub fn new(widget: Box<dyn Widget>) -> Self {
+        Self {
+            widget,
+            error: None,
+        }
+    }
+
+    /// # Return the panic which broke the widget, if any
+    pub fn error(&self) -> Option<&PanicError> {
+        self.error.as_ref()
+    }
+
+    /// # Forget the last panic and start calling the widget callbacks again
+    pub fn reset(&mut self) {
+        self.error = None;
+    }
+
+    /// # Return the wrapped widget
+    pub fn into_inner(self) -> Box<dyn Widget> {
+        self.widget
+    }
+
+    fn call<T, F: FnOnce(&mut dyn Widget) -> T>(
+        &mut self,
+        callback: &'static str,
+        f: F,
+    ) -> Result<T, Error> {
+        if let Some(error) = &self.error {
+            return Err(Error::Panic(error.clone()));
+        }
+        self.call_broken(callback, f)
+    }
+
+    /// Call the widget even if it is broken, the first panic is kept
+    fn call_broken<T, F: FnOnce(&mut dyn Widget) -> T>(
+        &mut self,
+        callback: &'static str,
+        f: F,
+    ) -> Result<T, Error> {
+        let widget = &mut *self.widget;
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| f(widget))).map_err(|payload| {
+            let error = self
+                .error
+                .get_or_insert_with(|| PanicError::new(callback, &*payload));
+            Error::Panic(error.clone())
+        })
+    }
+}
+
+impl Widget for GuardedWidget {
+    fn display(&mut self, height: i32, width: i32) -> String {
+        self.try_display(height, width)
+            .unwrap_or_else(|err| err.display(height, width))
+    }
+
+    fn try_display(&mut self, height: i32, width: i32) -> Result<String, Error> {
+        self.call("display", |widget| widget.try_display(height, width))
+            .and_then(|output| output)
+    }
+
+    fn render(&mut self, frame: &mut Frame) -> Result<(), Error> {
+        self.call("render", |widget| widget.render(frame))
+            .and_then(|output| output)
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        self.call("needs_redraw", |widget| widget.needs_redraw())
+            .unwrap_or(true)
+    }
+
+    fn on_resize(&mut self, height: i32, width: i32) {
+        self.call("on_resize", |widget| widget.on_resize(height, width))
+            .ok();
+    }
+
+    fn title(&mut self) -> Option<String> {
+        self.call("title", |widget| widget.title()).ok().flatten()
+    }
+
+    fn on_update(&mut self, elapsed: Duration) {
+        self.try_update(elapsed).ok();
+    }
+
+    fn try_update(&mut self, elapsed: Duration) -> Result<(), Error> {
+        self.call("on_update", |widget| widget.try_update(elapsed))
+            .and_then(|output| output)
+    }
+
+    fn update_interval(&mut self) -> Option<Duration> {
+        self.call("update_interval", |widget| widget.update_interval())
+            .ok()
+            .flatten()
+    }
+
+    fn on_input(&mut self, key: String) {
+        self.call("on_input", |widget| widget.on_input(key)).ok();
+    }
+
+    fn on_key(&mut self, key: KeyEvent) {
+        self.call("on_key", |widget| widget.on_key(key)).ok();
+    }
+
+    fn on_mouse(&mut self, event: MouseEvent) {
+        self.call("on_mouse", |widget| widget.on_mouse(event)).ok();
+    }
+
+    fn init(&mut self) {
+        self.call("init", |widget| widget.init()).ok();
+    }
+
+    fn on_focus(&mut self) {
+        self.call("on_focus", |widget| widget.on_focus()).ok();
+    }
+
+    fn on_blur(&mut self) {
+        self.call("on_blur", |widget| widget.on_blur()).ok();
+    }
+
+    fn on_show(&mut self) {
+        self.call("on_show", |widget| widget.on_show()).ok();
+    }
+
+    fn on_hide(&mut self) {
+        self.call("on_hide", |widget| widget.on_hide()).ok();
+    }
+
+    fn on_destroy(&mut self) {
+        self.call_broken("on_destroy", |widget| widget.on_destroy())
+            .ok();
+    }
+
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        self.call("save_state", |widget| widget.save_state())
+            .ok()
+            .flatten()
+    }
+
+    fn restore_state(&mut self, state: &[u8]) {
+        self.call("restore_state", |widget| widget.restore_state(state))
+            .ok();
+    }
+}
+
+/// Represent a panic caught by a [`GuardedWidget`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PanicError {
+    /// Name of the [`Widget`] method which panicked
+    pub callback: &'static str,
+    /// Message of the panic
+    pub message: String,
+}
+
+impl PanicError {
+    pub(crate) fn new(callback: &'static str, payload: &(dyn std::any::Any + Send)) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            String::from(*message)
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        };
+
+        Self { callback, message }
+    }
+
+    /// # Return the error placeholder displayed in place of the widget
+    /// ## Arguments
+    /// * `height` - The height of the placeholder
+    /// * `width` - The width of the placeholder
+    pub fn display(&self, height: i32, width: i32) -> String {
+        crate::error::placeholder(&self.to_string(), height, width)
+    }
+}
+
+impl std::fmt::Display for PanicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The widget panicked in `{}`: {}",
+            self.callback, self.message
+        )
+    }
+}
+
+impl std::error::Error for PanicError {}
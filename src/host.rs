@@ -0,0 +1,634 @@
+//! Module containing the tools to load Rtop's plugins outside of Rtop
+//!
+//! **⚠️ Only available with the `host` feature**
+//!
+//! ## Example
+//! ```rust,no_run
+//! use rtop_dev::host::LoadedPlugin;
+//!
+//! let mut plugin = LoadedPlugin::load("target/release/libmy_plugin.so").unwrap();
+//!
+//! loop {
+//!     plugin.update();
+//!     for widget in plugin.widgets_mut() {
+//!         widget.set_visible(true);
+//!         println!("{}", widget.display(10, 40));
+//!     }
+//!     plugin.reload_if_changed().unwrap(); // Hot-reload the plugin when it's rebuilt
+//!     std::thread::sleep(std::time::Duration::from_millis(100));
+//! }
+//! ```
+
+use crate::event::{KeyEvent, MouseEvent};
+use crate::ffi::{AbiMismatch, FfiWidget, ABI_VERSION};
+use crate::frame::Frame;
+use crate::manifest::{Manifest, ManifestError};
+use crate::version::Incompatibility;
+use crate::widget::WidgetDescriptor;
+use crate::Error;
+
+/// Prefix of the functions exported by [`export_widget!`](crate::export_widget) and loaded by the host
+pub const INIT_PREFIX: &str = "ffi_init_";
+
+/// Name of the function returning the ABI version of a plugin, see [`rtop_abi_version`](crate::ffi::rtop_abi_version)
+pub const ABI_VERSION_SYMBOL: &str = "rtop_abi_version";
+
+/// Plugin library loaded in memory, with all of its widgets
+pub struct LoadedPlugin {
+    // Declared before `library` so the widgets are dropped before the library is unloaded
+    widgets: Vec<LoadedWidget>,
+    manifest: Option<Manifest>,
+    path: std::path::PathBuf,
+    modified: Option<std::time::SystemTime>,
+    library: libloading::Library,
+}
+
+impl LoadedPlugin {
+    /// # Load a plugin, create all of its widgets and call their `init`
+    /// The ABI version of the library and the version compatibility of the manifest and of every widget are checked before calling any of their methods
+    /// ## Arguments
+    /// * `path` - Path to the `.so` file of the plugin<br>
+    /// **⚠️ Loading a library executes its initialisation code, only load trusted plugins**
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, HostError> {
+        Self::open(path.as_ref(), path.as_ref())
+    }
+
+    /// # Load the plugin again, and hand the state of every widget to its new instance
+    /// The new library is loaded, and every widget of the new version is created and checked first:
+    /// if anything fails, the error is returned and the current library and widgets are kept running.
+    /// Then `save_state` and `on_destroy` are called on the old widgets, before `init` is called on the new instances, which are restored, shown and focused like the old ones.
+    /// The library is copied before being loaded, so the new version is used even if the old one can't be unloaded.
+    pub fn reload(&mut self) -> Result<(), HostError> {
+        static RELOAD_COUNT: std::sync::atomic::AtomicUsize =
+            std::sync::atomic::AtomicUsize::new(0);
+
+        let copy = std::env::temp_dir().join(format!(
+            "rtop-{}-{}-{}",
+            std::process::id(),
+            RELOAD_COUNT.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            self.path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default()
+        ));
+        std::fs::copy(&self.path, &copy)?;
+        let plugin = Self::open_library(&self.path, &copy);
+        std::fs::remove_file(&copy).ok();
+        let (mut plugin, names) = plugin?;
+        let descriptors = plugin.create_descriptors(names)?;
+
+        let mut previous = std::collections::HashMap::new();
+        for widget in &mut self.widgets {
+            let state = widget.descriptor.widget.save_state();
+            previous.insert(widget.id.clone(), (state, widget.visible, widget.focused));
+        }
+        // Dropping the widgets calls their `on_destroy`, then the old library is unloaded
+        self.widgets.clear();
+        plugin.init_widgets(descriptors);
+        *self = plugin;
+
+        for widget in &mut self.widgets {
+            if let Some((state, visible, focused)) = previous.get(&widget.id) {
+                if let Some(state) = state {
+                    widget.descriptor.widget.restore_state(state);
+                }
+                widget.set_visible(*visible);
+                widget.set_focused(*focused);
+            }
+        }
+        Ok(())
+    }
+
+    /// # Reload the plugin if its file changed since it was loaded
+    /// Return `true` if the plugin was reloaded
+    pub fn reload_if_changed(&mut self) -> Result<bool, HostError> {
+        let modified = std::fs::metadata(&self.path)?.modified().ok();
+        if modified == self.modified {
+            return Ok(false);
+        }
+
+        self.reload()?;
+        Ok(true)
+    }
+
+    fn open(path: &std::path::Path, library_path: &std::path::Path) -> Result<Self, HostError> {
+        let (mut plugin, names) = Self::open_library(path, library_path)?;
+        let descriptors = plugin.create_descriptors(names)?;
+        plugin.init_widgets(descriptors);
+        Ok(plugin)
+    }
+
+    /// Load the library and check its compatibility, without creating any widget.
+    /// Return the plugin and the name of its widgets
+    fn open_library(
+        path: &std::path::Path,
+        library_path: &std::path::Path,
+    ) -> Result<(Self, Vec<String>), HostError> {
+        let modified = std::fs::metadata(path)?.modified().ok();
+        let names = exported_widgets(path)?;
+        let manifest = match Manifest::from_file(path) {
+            Ok(manifest) => Some(manifest),
+            Err(ManifestError::NotFound) => None,
+            Err(err) => return Err(HostError::Manifest(err)),
+        };
+        if let Some(manifest) = &manifest {
+            manifest.check()?;
+        }
+
+        let library = unsafe { libloading::Library::new(library_path)? };
+
+        // `FfiWidget` is returned by value, its layout must be checked before calling any `ffi_init_*` function.
+        // Plugins built before the symbol was added are reported with the version 0
+        let abi_version =
+            unsafe { library.get::<extern "C" fn() -> u32>(ABI_VERSION_SYMBOL.as_bytes()) }
+                .map_or(0, |abi_version| abi_version());
+        if abi_version != ABI_VERSION {
+            return Err(HostError::Incompatible(Incompatibility::Abi(AbiMismatch {
+                expected: ABI_VERSION,
+                found: abi_version,
+            })));
+        }
+
+        Ok((
+            Self {
+                widgets: vec![],
+                manifest,
+                path: path.to_path_buf(),
+                modified,
+                library,
+            },
+            names,
+        ))
+    }
+
+    /// Create and check the descriptors of the widgets exported with the given names, without calling any of their methods
+    fn create_descriptors(
+        &self,
+        names: Vec<String>,
+    ) -> Result<Vec<(String, WidgetDescriptor)>, HostError> {
+        let mut descriptors = Vec::with_capacity(names.len());
+        for name in names {
+            let init = unsafe {
+                self.library.get::<extern "C" fn() -> FfiWidget>(
+                    format!("{}{}", INIT_PREFIX, name).as_bytes(),
+                )?
+            };
+            let descriptor = init().into_descriptor().map_err(|err| match err {
+                Error::Incompatible(err) => HostError::Incompatible(err),
+                err => HostError::Widget {
+                    name: name.clone(),
+                    error: Box::new(err),
+                },
+            })?;
+            descriptors.push((name, descriptor));
+        }
+        Ok(descriptors)
+    }
+
+    /// Add the widgets created by `create_descriptors` to the plugin and call their `init`
+    fn init_widgets(&mut self, descriptors: Vec<(String, WidgetDescriptor)>) {
+        for (id, mut descriptor) in descriptors {
+            descriptor.widget.init();
+            self.widgets.push(LoadedWidget {
+                id,
+                descriptor,
+                last_update: std::time::Instant::now(),
+                error: None,
+                focused: false,
+                visible: false,
+                size: None,
+                output: None,
+                frame: None,
+            });
+        }
+    }
+
+    /// # Return the manifest of the plugin, if it exports one
+    pub fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
+    /// # Return the path the plugin was loaded from
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// # Return all the widgets of the plugin
+    pub fn widgets(&self) -> &[LoadedWidget] {
+        &self.widgets
+    }
+
+    /// # Return all the widgets of the plugin
+    pub fn widgets_mut(&mut self) -> &mut [LoadedWidget] {
+        &mut self.widgets
+    }
+
+    /// # Return the widget exported with the given name
+    /// ## Arguments
+    /// * `id` - The name used with [`export_widget!`](crate::export_widget)
+    pub fn widget_mut(&mut self, id: &str) -> Option<&mut LoadedWidget> {
+        self.widgets.iter_mut().find(|widget| widget.id == id)
+    }
+
+    /// # Call `on_update` on every widget whose refresh interval is elapsed
+    /// Each widget has its own refresh interval, call this method at least as often as the shortest one
+    pub fn update(&mut self) {
+        for widget in &mut self.widgets {
+            widget.update();
+        }
+    }
+}
+
+/// Widget created from a [`LoadedPlugin`]
+/// A widget starts hidden and not focused, its `on_destroy` is called when it is dropped
+pub struct LoadedWidget {
+    id: String,
+    descriptor: WidgetDescriptor,
+    last_update: std::time::Instant,
+    error: Option<Error>,
+    focused: bool,
+    visible: bool,
+    size: Option<(i32, i32)>,
+    output: Option<String>,
+    frame: Option<Frame>,
+}
+
+impl LoadedWidget {
+    /// # Return the name the widget was exported with
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// # Return the [`WidgetDescriptor`] of the widget
+    pub fn descriptor(&self) -> &WidgetDescriptor {
+        &self.descriptor
+    }
+
+    /// # Return the error returned by the last update of the widget, if any
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    /// # Return `true` if the widget has the focus of the user
+    pub fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// # Return `true` if the widget is visible for the user
+    pub fn visible(&self) -> bool {
+        self.visible
+    }
+
+    /// # Give or remove the focus to the widget
+    /// `on_focus` or `on_blur` is only called if the focus changes
+    /// ## Arguments
+    /// * `focused` - `true` if the widget gains the focus
+    pub fn set_focused(&mut self, focused: bool) {
+        if focused == self.focused {
+            return;
+        }
+        self.focused = focused;
+        if focused {
+            self.descriptor.widget.on_focus();
+        } else {
+            self.descriptor.widget.on_blur();
+        }
+    }
+
+    /// # Show or hide the widget
+    /// `on_show` or `on_hide` is only called if the visibility changes, `display` and `render` don't call a hidden widget
+    /// ## Arguments
+    /// * `visible` - `true` if the widget becomes visible
+    pub fn set_visible(&mut self, visible: bool) {
+        if visible == self.visible {
+            return;
+        }
+        self.visible = visible;
+        if visible {
+            self.descriptor.widget.on_show();
+        } else {
+            self.descriptor.widget.on_hide();
+        }
+    }
+
+    /// # Call `try_update` if the refresh interval of the widget is elapsed
+    /// The refresh interval is then updated with the one returned by `update_interval`, if any.
+    /// Return `true` if the widget was updated
+    pub fn update(&mut self) -> bool {
+        let elapsed = self.last_update.elapsed();
+        if elapsed < self.descriptor.refresh_interval {
+            return false;
+        }
+        self.last_update = std::time::Instant::now();
+        self.error = self.descriptor.widget.try_update(elapsed).err();
+        if let Some(interval) = self.descriptor.widget.update_interval() {
+            self.descriptor.refresh_interval = interval;
+        }
+        true
+    }
+
+    /// # Return the output of the widget
+    /// `on_resize` is called first if the size changed since the previous call.
+    /// The previous output is reused if the widget doesn't need a redraw, see [`Widget::needs_redraw`](crate::widget::Widget::needs_redraw).
+    /// If the last update or the display failed, a placeholder describing the error is returned instead.<br>
+    /// The widget isn't called while it is hidden: the previous output is returned if the size didn't change, blank lines otherwise
+    /// ## Arguments
+    /// * `height` - The height allocated to the widget
+    /// * `width` - The width allocated to the widget
+    pub fn display(&mut self, height: i32, width: i32) -> String {
+        if !self.visible {
+            return match &self.output {
+                Some(output) if self.size == Some((height, width)) => output.clone(),
+                _ => Frame::new(height, width).to_markup(),
+            };
+        }
+        self.resize(height, width);
+        if let Some(error) = &self.error {
+            self.output = None;
+            return error.display(height, width);
+        }
+
+        if let Some(output) = &self.output {
+            if !self.descriptor.widget.needs_redraw() {
+                return output.clone();
+            }
+        }
+        match self.descriptor.widget.try_display(height, width) {
+            Ok(output) => {
+                self.output = Some(output.clone());
+                output
+            }
+            Err(err) => {
+                self.output = None;
+                err.display(height, width)
+            }
+        }
+    }
+
+    /// # Return the output of the widget as a [`Frame`] of styled cells
+    /// Behave like `display`, but call [`Widget::render`](crate::widget::Widget::render) instead
+    /// ## Arguments
+    /// * `height` - The height allocated to the widget
+    /// * `width` - The width allocated to the widget
+    pub fn render(&mut self, height: i32, width: i32) -> Frame {
+        if !self.visible {
+            return match &self.frame {
+                Some(frame) if self.size == Some((height, width)) => frame.clone(),
+                _ => Frame::new(height, width),
+            };
+        }
+        self.resize(height, width);
+        if let Some(error) = &self.error {
+            self.frame = None;
+            return Frame::from_markup(&error.display(height, width), height, width);
+        }
+
+        if let Some(frame) = &self.frame {
+            if !self.descriptor.widget.needs_redraw() {
+                return frame.clone();
+            }
+        }
+        let mut frame = Frame::new(height, width);
+        match self.descriptor.widget.render(&mut frame) {
+            Ok(()) => {
+                self.frame = Some(frame.clone());
+                frame
+            }
+            Err(err) => {
+                self.frame = None;
+                Frame::from_markup(&err.display(height, width), height, width)
+            }
+        }
+    }
+
+    fn resize(&mut self, height: i32, width: i32) {
+        if self.size != Some((height, width)) {
+            self.size = Some((height, width));
+            self.output = None;
+            self.frame = None;
+            self.descriptor.widget.on_resize(height, width);
+        }
+    }
+
+    /// # Return the title of the widget, if it defines one
+    pub fn title(&mut self) -> Option<String> {
+        self.descriptor.widget.title()
+    }
+
+    /// # Send a key to the widget, ignored if the widget doesn't accept input
+    /// ## Arguments
+    /// * `key` - The key pressed by the user, encoded as described in [`event`](crate::event)
+    pub fn input(&mut self, key: &str) {
+        self.key(KeyEvent::parse(key));
+    }
+
+    /// # Send a parsed key to the widget, ignored if the widget doesn't accept input
+    /// ## Arguments
+    /// * `key` - The key pressed by the user
+    pub fn key(&mut self, key: KeyEvent) {
+        if self.descriptor.input.keyboard() {
+            self.descriptor.widget.on_key(key);
+        }
+    }
+
+    /// # Send a mouse event to the widget, ignored if the widget doesn't accept the mouse
+    /// ## Arguments
+    /// * `event` - The mouse event, with coordinates relative to the widget
+    pub fn mouse(&mut self, event: MouseEvent) {
+        if self.descriptor.input.mouse() {
+            self.descriptor.widget.on_mouse(event);
+        }
+    }
+}
+
+impl Drop for LoadedWidget {
+    fn drop(&mut self) {
+        self.descriptor.widget.on_destroy();
+    }
+}
+
+/// Return the name of every widget exported by a plugin
+/// The names are read from the dynamic symbols starting with [`INIT_PREFIX`], none of the library code is executed
+/// ## Arguments
+/// * `path` - Path to the `.so` file of the plugin
+pub fn exported_widgets<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<String>, HostError> {
+    let content = std::fs::read(path)?;
+    check_elf_header(&content)?;
+    let symbols = dynamic_symbols(&content)
+        .ok_or_else(|| HostError::Format(String::from("the ELF library is malformed")))?;
+
+    Ok(symbols
+        .into_iter()
+        .filter_map(|symbol| symbol.strip_prefix(INIT_PREFIX).map(String::from))
+        .filter(|name| !name.is_empty())
+        .collect())
+}
+
+/// Error returned when a plugin can't be loaded
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HostError {
+    /// The library can't be read
+    Io(std::io::Error),
+    /// The library isn't a shared library the host can understand
+    Format(String),
+    /// The library can't be loaded, or a symbol is missing
+    Library(libloading::Error),
+    /// The manifest of the plugin is invalid
+    Manifest(ManifestError),
+    /// The plugin is incompatible with the host
+    Incompatible(Incompatibility),
+    /// A widget of the plugin can't be created, such as when its constructor panicked
+    Widget {
+        /// Name the widget is exported with
+        name: String,
+        /// Error returned while creating the widget
+        error: Box<Error>,
+    },
+}
+
+impl std::fmt::Display for HostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "unable to read the plugin: {}", err),
+            Self::Format(reason) => write!(f, "invalid plugin: {}", reason),
+            Self::Library(err) => write!(f, "unable to load the plugin: {}", err),
+            Self::Manifest(err) => err.fmt(f),
+            Self::Incompatible(err) => err.fmt(f),
+            Self::Widget { name, error } => {
+                write!(f, "unable to create the widget `{}`: {}", name, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HostError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Library(err) => Some(err),
+            Self::Manifest(err) => Some(err),
+            Self::Incompatible(err) => Some(err),
+            Self::Widget { error, .. } => Some(&**error),
+            Self::Format(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for HostError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<libloading::Error> for HostError {
+    fn from(err: libloading::Error) -> Self {
+        Self::Library(err)
+    }
+}
+
+impl From<Incompatibility> for HostError {
+    fn from(err: Incompatibility) -> Self {
+        Self::Incompatible(err)
+    }
+}
+
+/// Check the identification bytes of the ELF header, only little-endian ELF64 files can be read by `dynamic_symbols`
+fn check_elf_header(content: &[u8]) -> Result<(), HostError> {
+    const ELFCLASS64: u8 = 2;
+    const ELFDATA2LSB: u8 = 1;
+
+    if content.get(..4) != Some(b"\x7fELF") {
+        return Err(HostError::Format(String::from(
+            "the plugin is not an ELF library",
+        )));
+    }
+    if content.get(4) != Some(&ELFCLASS64) {
+        return Err(HostError::Format(String::from(
+            "only 64-bit ELF libraries are supported",
+        )));
+    }
+    if content.get(5) != Some(&ELFDATA2LSB) {
+        return Err(HostError::Format(String::from(
+            "only little-endian ELF libraries are supported",
+        )));
+    }
+    Ok(())
+}
+
+/// Return the name of the functions defined in the `.dynsym` section of a little-endian ELF64 file, checked by `check_elf_header`
+/// Every offset is checked, `None` is returned if the file is truncated or malformed
+fn dynamic_symbols(content: &[u8]) -> Option<Vec<String>> {
+    const SHT_DYNSYM: u32 = 11;
+    const STT_FUNC: u8 = 2;
+
+    let section_offset = usize::try_from(read_u64(content, 0x28)?).ok()?;
+    let section_size = usize::from(read_u16(content, 0x3A)?);
+    let section_count = usize::from(read_u16(content, 0x3C)?);
+    let section = |index: usize| {
+        let start = index
+            .checked_mul(section_size)?
+            .checked_add(section_offset)?;
+        content.get(start..)?.get(..section_size)
+    };
+
+    let mut symbols = vec![];
+    for index in 0..section_count {
+        let header = section(index)?;
+        if read_u32(header, 4)? != SHT_DYNSYM {
+            continue;
+        }
+
+        let strings = section(usize::try_from(read_u32(header, 40)?).ok()?)?;
+        let strings_offset = usize::try_from(read_u64(strings, 24)?).ok()?;
+        let table = content
+            .get(usize::try_from(read_u64(header, 24)?).ok()?..)?
+            .get(..usize::try_from(read_u64(header, 32)?).ok()?)?;
+        let entry_size = usize::try_from(read_u64(header, 56)?).ok()?;
+        if entry_size == 0 {
+            return None;
+        }
+
+        for symbol in table.chunks_exact(entry_size) {
+            let defined = read_u16(symbol, 6)? != 0;
+            if !defined || symbol.get(4)? & 0xF != STT_FUNC {
+                continue;
+            }
+
+            let name_offset =
+                strings_offset.checked_add(usize::try_from(read_u32(symbol, 0)?).ok()?)?;
+            let name = content.get(name_offset..)?;
+            let end = name.iter().position(|byte| *byte == 0)?;
+            symbols.push(String::from_utf8_lossy(&name[..end]).into_owned());
+        }
+    }
+
+    Some(symbols)
+}
+
+fn read_u16(content: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        content
+            .get(offset..offset.checked_add(2)?)?
+            .try_into()
+            .ok()?,
+    ))
+}
+
+fn read_u32(content: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        content
+            .get(offset..offset.checked_add(4)?)?
+            .try_into()
+            .ok()?,
+    ))
+}
+
+fn read_u64(content: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        content
+            .get(offset..offset.checked_add(8)?)?
+            .try_into()
+            .ok()?,
+    ))
+}
@@ -1,7 +1,7 @@
 //! Rtop SDK to create plugin, made with ❤️ for you.
 //! ## Example Usage
 //! Firstly, create a new project for your plugin!
-//! ```
+//! ```text
 //! cargo new --lib MyPlugin
 //! ```
 //! After that, update your `Cargo.toml` file. It should look like that:
@@ -12,7 +12,7 @@
 //! edition = "2021"
 //!
 //! [dependencies]
-//! rtop_dev = "^1.0.0"
+//! rtop_dev = "^2.0.0"
 //!
 //! [lib]
 //! name = "my_plugin"
@@ -20,24 +20,24 @@
 //!
 //! [profile.release]
 //! codegen-units = 1
-//! panic = "abort"
 //! strip = true
 //! lto = true
 //! ```
 //! Then, edit your `src/lib.rs` to have somethings like this:
 //! ```rust
+//! #[derive(Default)]
 //! struct FooWidget {}
 //!
-//! impl rtop_dev::plugin::Plugin for FooWidget {
+//! impl rtop_dev::widget::Widget for FooWidget {
 //!     fn display(&mut self, _height: i32, _width: i32) -> String {
 //!         String::from("Hello World RTop!")
 //!     }
 //! }
 //!
-//! #[no_mangle]
-//! pub extern "Rust" fn init_foo() -> (Box<dyn rtop_dev::plugin::Plugin>, bool) {
-//!     (Box::new(FooWidget{}), false)
-//! }
+//! rtop_dev::export_widget!(FooWidget, name = foo, display_name = "Foo");
+//! rtop_dev::export_manifest!(name = "Foo", author = "Me", widgets = [foo]);
+//! ```
+//! Rtop creates a widget for each `ffi_init_{name}` function exported by the library, see [`export_widget!`] and [`ffi`].
 
 #![allow(
     clippy::implicit_return,
@@ -59,17 +59,16 @@
     clippy::exhaustive_structs,
     clippy::single_char_lifetime_names,
     clippy::integer_division,
-    clippy::separated_literal_suffix,
-    clippy::else_if_without_else,
     clippy::indexing_slicing,
     clippy::cast_possible_truncation,
-    clippy::integer_arithmetic,
+    clippy::arithmetic_side_effects,
     clippy::default_numeric_fallback,
     clippy::cast_possible_wrap,
     clippy::cast_sign_loss,
     clippy::float_arithmetic,
     clippy::pattern_type_mismatch,
-    clippy::as_conversions
+    clippy::as_conversions,
+    clippy::doc_lazy_continuation
 )]
 #![deny(
     clippy::needless_return,
@@ -80,11 +79,31 @@
     clippy::wildcard_imports,
     clippy::single_match_else,
     clippy::single_match,
-    clippy::let_underscore_drop,
     clippy::expect_used,
     clippy::suboptimal_flops,
-    clippy::redundant_else
+    clippy::redundant_else,
+    let_underscore_drop
 )]
 
+pub mod ansi;
 pub mod components;
+mod error;
+pub mod event;
+pub mod ffi;
+pub mod frame;
+pub mod guard;
+#[cfg(feature = "host")]
+pub mod host;
+mod macros;
+pub mod manifest;
+pub mod markup;
+pub mod style;
+pub mod testing;
+pub mod text;
+pub mod version;
 pub mod widget;
+
+pub use error::Error;
+
+/// Version of `rtop_dev`
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
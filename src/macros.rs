@@ -0,0 +1,119 @@
+//! Module containing the macros used to export the content of a plugin and to test it
+
+/// # Export a [`Widget`](crate::widget::Widget) from your plugin
+/// Generate the `ffi_init_{name}` function loaded by Rtop, see [`ffi`](crate::ffi), so you never have to write it by hand.
+/// The function returns the [`WidgetDescriptor`](crate::widget::WidgetDescriptor) of the widget, through the C ABI.<br>
+/// **⚠️ The widget is created with its `Default` implementation**
+/// ## Arguments
+/// * `widget` - The type of your widget
+/// * `name` - The name of the widget, used to build the name of the exported function
+/// * `display_name` - *`Optional`* - If supplied, set the human-readable name of the widget, otherwise, `name` will be used
+/// * `input` - *`Optional`* - If supplied, set the [`InputMode`](crate::widget::InputMode) of the widget, otherwise, it will be `None`
+/// * `size` - *`Optional`* - If supplied, set the preferred `(height, width)` of the widget
+/// * `min_size` - *`Optional`* - If supplied, set the minimal `(height, width)` of the widget
+/// * `refresh_interval` - *`Optional`* - If supplied, set the `Duration` between two updates of the widget
+/// ## Example
+/// ```rust
+/// #[derive(Default)]
+/// struct FooWidget {}
+///
+/// impl rtop_dev::widget::Widget for FooWidget {
+///     fn display(&mut self, _height: i32, _width: i32) -> String {
+///         String::from("Hello World RTop!")
+///     }
+/// }
+///
+/// rtop_dev::export_widget!(FooWidget, name = foo, display_name = "Foo", input = Keyboard, size = (10, 40));
+/// ```
+#[macro_export]
+macro_rules! export_widget {
+    (@display_name $name:ident) => {
+        stringify!($name)
+    };
+    (@display_name $name:ident $display_name:expr) => {
+        $display_name
+    };
+    (
+        $widget:ty,
+        name = $name:ident
+        $(, display_name = $display_name:expr)?
+        $(, input = $input:ident)?
+        $(, size = ($height:expr, $width:expr))?
+        $(, min_size = ($min_height:expr, $min_width:expr))?
+        $(, refresh_interval = $refresh_interval:expr)?
+        $(,)?
+    ) => {
+        const _: () = {
+            fn descriptor() -> $crate::widget::WidgetDescriptor {
+                #[allow(unused_mut)]
+                let mut descriptor = $crate::widget::WidgetDescriptor::new(
+                    ::std::boxed::Box::new(<$widget as ::core::default::Default>::default()),
+                    $crate::export_widget!(@display_name $name $($display_name)?),
+                );
+                $(descriptor.input = $crate::widget::InputMode::$input;)?
+                $(descriptor.preferred_size = Some(($height, $width));)?
+                $(descriptor.min_size = Some(($min_height, $min_width));)?
+                $(descriptor.refresh_interval = $refresh_interval;)?
+                descriptor
+            }
+
+            #[export_name = concat!("ffi_init_", stringify!($name))]
+            pub extern "C" fn ffi_init() -> $crate::ffi::FfiWidget {
+                $crate::ffi::FfiWidget::from_init(descriptor)
+            }
+        };
+    };
+}
+
+/// # Export the [`Manifest`](crate::manifest::Manifest) of your plugin
+/// Generate the `RTOP_MANIFEST` static read by Rtop before loading any widget.
+/// ## Arguments
+/// * `name` - The human-readable name of the plugin
+/// * `author` - The author of the plugin
+/// * `version` - *`Optional`* - If supplied, set the version of the plugin, otherwise, the version of your crate will be used
+/// * `widgets` - The name of every widget exported with [`export_widget!`](crate::export_widget)
+/// ## Example
+/// ```rust
+/// rtop_dev::export_manifest!(name = "Foo", author = "Me", version = "0.1.0", widgets = [foo]);
+/// ```
+#[macro_export]
+macro_rules! export_manifest {
+    (@version) => {
+        env!("CARGO_PKG_VERSION")
+    };
+    (@version $version:expr) => {
+        $version
+    };
+    (name = $name:expr, author = $author:expr $(, version = $version:expr)?, widgets = [$($widget:ident),* $(,)?] $(,)?) => {
+        #[no_mangle]
+        #[used]
+        pub static RTOP_MANIFEST: $crate::manifest::EmbeddedManifest = $crate::manifest::EmbeddedManifest::new(
+            $name,
+            $author,
+            $crate::export_manifest!(@version $($version)?),
+            &[$(stringify!($widget)),*],
+        );
+    };
+}
+
+/// # Compare the output of a widget or a component with a snapshot
+/// The snapshot is stored in `snapshots/{name}.snap`, next to the file calling the macro, see [`testing`](crate::testing) for its format.<br>
+/// **⚠️ Panic with a diff of the snapshot if it doesn't match**
+/// ## Arguments
+/// * `name` - The name of the snapshot, unique in the test file
+/// * `output` - The output to compare, as a `&str`, a `String` or a [`Screen`](crate::testing::Screen)
+/// ## Example
+/// ```rust,no_run
+/// use rtop_dev::components::bar::Horizontal;
+///
+/// rtop_dev::assert_snapshot!("horizontal_half", Horizontal::new(1, 10, None).display(50.0));
+/// ```
+#[macro_export]
+macro_rules! assert_snapshot {
+    ($name:expr, $output:expr) => {
+        $crate::testing::assert_snapshot(
+            $crate::testing::snapshot_path(env!("CARGO_MANIFEST_DIR"), file!(), $name),
+            &$crate::testing::Screen::from($output),
+        )
+    };
+}
@@ -0,0 +1,231 @@
+//! Module containing the manifest of a plugin
+//!
+//! The manifest is embedded in the library as a static buffer, so Rtop can read it before instantiating any [`Widget`](crate::widget::Widget), without even loading the library.
+//!
+//! ## Example
+//! ```rust
+//! rtop_dev::export_manifest!(name = "Foo", author = "Me", widgets = [foo, bar]);
+//!
+//! // In Rtop, `Manifest::from_file("libfoo.so")` would return the same manifest
+//! let manifest = RTOP_MANIFEST.manifest().unwrap();
+//! assert_eq!(manifest.name, "Foo");
+//! assert_eq!(manifest.widgets, vec![String::from("foo"), String::from("bar")]);
+//! ```
+
+use crate::version::{Incompatibility, Requirement, Version};
+
+/// Header placed in front of every embedded manifest, used to find it inside a library
+pub const MAGIC: &[u8] = b"RTOP_MANIFEST_V1\n";
+
+/// Size in bytes of an [`EmbeddedManifest`]
+pub const MANIFEST_SIZE: usize = 1024;
+
+/// Manifest as stored inside the library, created by the [`export_manifest!`](crate::export_manifest) macro
+#[repr(C)]
+pub struct EmbeddedManifest {
+    bytes: [u8; MANIFEST_SIZE],
+}
+
+impl EmbeddedManifest {
+    /// # Create a new `EmbeddedManifest` at compile time
+    /// ## Arguments
+    /// * `name` - The human-readable name of the plugin
+    /// * `author` - The author of the plugin
+    /// * `version` - The version of the plugin
+    /// * `widgets` - The name of every widget exported by the plugin<br>
+    /// **⚠️ Values can't contain line breaks, and widget names can't contain commas**
+    pub const fn new(name: &str, author: &str, version: &str, widgets: &[&str]) -> Self {
+        let mut manifest = Self {
+            bytes: [0; MANIFEST_SIZE],
+        };
+        let mut pos = manifest.push(0, MAGIC);
+        pos = manifest.push_field(pos, "name", name);
+        pos = manifest.push_field(pos, "author", author);
+        pos = manifest.push_field(pos, "version", version);
+        pos = manifest.push_field(pos, "rtop_dev", crate::VERSION);
+
+        pos = manifest.push(pos, b"widgets=");
+        let mut i = 0;
+        while i < widgets.len() {
+            if i > 0 {
+                pos = manifest.push(pos, b",");
+            }
+            let widget = widgets[i].as_bytes();
+            let mut j = 0;
+            while j < widget.len() {
+                assert!(widget[j] != b',', "widget names can't contain commas");
+                j += 1;
+            }
+            pos = manifest.push_value(pos, widget);
+            i += 1;
+        }
+        manifest.push(pos, b"\n");
+
+        manifest
+    }
+
+    /// # Parse the `EmbeddedManifest` into a [`Manifest`]
+    pub fn manifest(&self) -> Result<Manifest, ManifestError> {
+        Manifest::parse(&self.bytes)
+    }
+
+    const fn push_field(&mut self, pos: usize, key: &str, value: &str) -> usize {
+        let mut pos = self.push(pos, key.as_bytes());
+        pos = self.push(pos, b"=");
+        pos = self.push_value(pos, value.as_bytes());
+        self.push(pos, b"\n")
+    }
+
+    const fn push_value(&mut self, pos: usize, value: &[u8]) -> usize {
+        let mut i = 0;
+        while i < value.len() {
+            assert!(
+                value[i] != b'\n' && value[i] != 0,
+                "manifest values can't contain line breaks"
+            );
+            i += 1;
+        }
+        self.push(pos, value)
+    }
+
+    const fn push(&mut self, pos: usize, value: &[u8]) -> usize {
+        assert!(
+            pos + value.len() < MANIFEST_SIZE,
+            "the manifest is too large"
+        );
+        let mut i = 0;
+        while i < value.len() {
+            self.bytes[pos + i] = value[i];
+            i += 1;
+        }
+        pos + value.len()
+    }
+}
+
+/// Information describing a plugin, read from its [`EmbeddedManifest`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    /// The human-readable name of the plugin
+    pub name: String,
+    /// The author of the plugin
+    pub author: String,
+    /// The version of the plugin
+    pub version: String,
+    /// The version of `rtop_dev` the plugin was built with
+    pub rtop_dev_version: String,
+    /// The name of every widget exported by the plugin
+    pub widgets: Vec<String>,
+}
+
+impl Manifest {
+    /// # Read the manifest embedded in a plugin library
+    /// The library is only read as a file, none of its code is executed
+    /// ## Arguments
+    /// * `path` - Path to the `.so` file of the plugin
+    pub fn from_file<P: AsRef<std::path::Path>>(path: P) -> Result<Self, ManifestError> {
+        let content = std::fs::read(path)?;
+        let mut error = ManifestError::NotFound;
+
+        let mut offset = 0;
+        while let Some(found) = find(&content[offset..], MAGIC) {
+            match Self::parse(&content[offset + found..]) {
+                Ok(manifest) => return Ok(manifest),
+                Err(err) => error = err,
+            }
+            offset += found + MAGIC.len();
+        }
+
+        Err(error)
+    }
+
+    /// # Check that the plugin can be used by the current host, see [`version::check`](crate::version::check)
+    pub fn check(&self) -> Result<(), Incompatibility> {
+        let version = Version::parse(&self.rtop_dev_version)
+            .ok_or_else(|| Incompatibility::InvalidVersion(self.rtop_dev_version.clone()))?;
+        crate::version::check(version, &Requirement::compatible_with(version))
+    }
+
+    /// # Parse a manifest from its raw bytes
+    /// ## Arguments
+    /// * `bytes` - Bytes starting with the [`MAGIC`] header, the manifest end at the first null byte
+    pub fn parse(bytes: &[u8]) -> Result<Self, ManifestError> {
+        let content = bytes.strip_prefix(MAGIC).ok_or(ManifestError::NotFound)?;
+        let end = content
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(content.len());
+        let content = std::str::from_utf8(&content[..end]).map_err(|_| {
+            ManifestError::Malformed(String::from("the manifest is not valid UTF-8"))
+        })?;
+
+        let mut fields = std::collections::HashMap::new();
+        for line in content.lines() {
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ManifestError::Malformed(format!("invalid line `{}`", line)))?;
+            fields.insert(key, value);
+        }
+
+        let field = |key: &str| {
+            fields
+                .get(key)
+                .map(|value| String::from(*value))
+                .ok_or_else(|| ManifestError::Malformed(format!("missing field `{}`", key)))
+        };
+
+        Ok(Self {
+            name: field("name")?,
+            author: field("author")?,
+            version: field("version")?,
+            rtop_dev_version: field("rtop_dev")?,
+            widgets: field("widgets")?
+                .split(',')
+                .filter(|widget| !widget.is_empty())
+                .map(String::from)
+                .collect(),
+        })
+    }
+}
+
+/// Error returned when a [`Manifest`] can't be read
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ManifestError {
+    /// The library can't be read
+    Io(std::io::Error),
+    /// The library doesn't contain any manifest
+    NotFound,
+    /// The manifest is present but invalid
+    Malformed(String),
+}
+
+impl std::fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "unable to read the plugin: {}", err),
+            Self::NotFound => write!(f, "the plugin doesn't contain any manifest"),
+            Self::Malformed(reason) => write!(f, "malformed manifest: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ManifestError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ManifestError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
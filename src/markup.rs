@@ -0,0 +1,381 @@
+//! Module containing a builder for the `[[EFFECT_*]]` markup displayed by Rtop
+//!
+//! The output of [`Widget::display`](crate::widget::Widget::display) is a string where effects are enabled and disabled by the same marker:
+//! * `[[EFFECT_COLOR_<TEXT>_<BACKGROUND>]]` - Color the text, with `BLACK`, `RED`, `GREEN`, `YELLOW`, `BLUE`, `MAGENTA`, `CYAN` or `WHITE`
+//! * `[[EFFECT_BOLD]]`, `[[EFFECT_ITALIC]]`, `[[EFFECT_UNDERLINE]]` and `[[EFFECT_REVERSE]]` - Change the appearance of the text
+//!
+//! [`Span`] and [`Line`] write these markers for you, from a [`Style`], and [`parse`] reads them back while reporting the invalid ones.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::markup::{Line, Span};
+//! use rtop_dev::style::{Color, Style};
+//!
+//! let line = Line::from(vec![
+//!     Span::raw("CPU "),
+//!     Span::styled("98%", Style::new().fg(Color::Red).bg(Color::Black).bold()),
+//! ]);
+//!
+//! assert_eq!(line.width(), 7);
+//! assert_eq!(
+//!     line.to_string(),
+//!     "CPU [[EFFECT_COLOR_RED_BLACK]][[EFFECT_BOLD]]98%[[EFFECT_BOLD]][[EFFECT_COLOR_RED_BLACK]]"
+//! );
+//!
+//! let markup = rtop_dev::markup::parse(&format!("{}\n[[EFFECT_BOLD]]Oops", line));
+//! assert_eq!(markup.lines[0], line);
+//! assert_eq!(markup.widths(), vec![7, 4]);
+//! assert_eq!(markup.errors[0].to_string(), "2:1 `[[EFFECT_BOLD]]` is never closed");
+//! ```
+
+use crate::style::{Color, Style};
+
+/// Represent a piece of text displayed with a single [`Style`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    /// The text, without markers and line breaks
+    pub content: String,
+    /// The style of the text
+    pub style: Style,
+}
+
+impl Span {
+    /// # Create a new `Span` without any style
+    /// ## Arguments
+    /// * `content` - The text of the span
+    pub fn raw(content: &str) -> Self {
+        Self::styled(content, Style::NONE)
+    }
+
+    /// # Create a new `Span`
+    /// ## Arguments
+    /// * `content` - The text of the span
+    /// * `style` - The style of the text
+    pub fn styled(content: &str, style: Style) -> Self {
+        Self {
+            content: String::from(content),
+            style,
+        }
+    }
+
+    /// # Return the number of cells the span takes once displayed
+    pub fn width(&self) -> usize {
+        crate::text::width(&self.content)
+    }
+}
+
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::raw(content)
+    }
+}
+
+impl std::fmt::Display for Span {
+    /// Write the content surrounded by the markers of the style
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.style.paint(&self.content))
+    }
+}
+
+/// Represent a line of text, made of several [`Span`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    /// The spans of the line, from left to right
+    pub spans: Vec<Span>,
+}
+
+impl Line {
+    /// # Create a new empty `Line`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// # Add a span at the end of the line
+    /// ## Arguments
+    /// * `span` - The span to add
+    pub fn push<S: Into<Span>>(&mut self, span: S) {
+        self.spans.push(span.into());
+    }
+
+    /// # Add a span at the end of the line and return the line
+    /// ## Arguments
+    /// * `span` - The span to add
+    pub fn with<S: Into<Span>>(mut self, span: S) -> Self {
+        self.push(span);
+        self
+    }
+
+    /// # Return the number of cells the line takes once displayed
+    pub fn width(&self) -> usize {
+        self.spans.iter().map(Span::width).sum()
+    }
+
+    /// # Return the text of the line, without markers
+    pub fn text(&self) -> String {
+        self.spans.iter().map(|span| &*span.content).collect()
+    }
+}
+
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+}
+
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Self { spans: vec![span] }
+    }
+}
+
+impl From<&str> for Line {
+    fn from(content: &str) -> Self {
+        Self::from(Span::raw(content))
+    }
+}
+
+impl FromIterator<Span> for Line {
+    fn from_iter<I: IntoIterator<Item = Span>>(iter: I) -> Self {
+        Self {
+            spans: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for Line {
+    /// Write every span with its markers, without line break
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for span in &self.spans {
+            span.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of [`parse`], the markup split into styled lines and the errors found in it
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Markup {
+    /// The lines of the markup, a line break at the end of the markup doesn't start a new line
+    pub lines: Vec<Line>,
+    /// The invalid markers, ordered by position
+    pub errors: Vec<MarkupError>,
+}
+
+impl Markup {
+    /// # Return `true` if no error was found
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// # Return the number of cells each line takes once displayed
+    pub fn widths(&self) -> Vec<usize> {
+        self.lines.iter().map(Line::width).collect()
+    }
+
+    /// # Return the text of the markup, without markers
+    pub fn text(&self) -> String {
+        self.lines
+            .iter()
+            .map(Line::text)
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Represent an invalid marker found by [`parse`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkupError {
+    /// The reason of the error
+    pub kind: MarkupErrorKind,
+    /// The line of the marker, starting at 1
+    pub line: usize,
+    /// The column of the first character of the marker, starting at 1 and counting the characters of the markers
+    pub column: usize,
+}
+
+impl std::fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{} ", self.line, self.column)?;
+        match &self.kind {
+            MarkupErrorKind::UnknownTag(tag) => write!(f, "unknown marker `[[EFFECT_{}]]`", tag),
+            MarkupErrorKind::Unterminated(tag) => {
+                write!(f, "`[[EFFECT_{}]]` is never closed", tag)
+            }
+            MarkupErrorKind::UnclosedMarker => f.write_str("`[[EFFECT_` without `]]`"),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+/// Reason of a [`MarkupError`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum MarkupErrorKind {
+    /// The effect or the colors of the marker don't exist, the marker is displayed as text
+    UnknownTag(String),
+    /// The effect is still enabled at the end of the markup
+    Unterminated(String),
+    /// A marker is started but its `]]` is missing on the line
+    UnclosedMarker,
+}
+
+/// # Parse a string using the `[[EFFECT_*]]` markers into styled lines
+/// Each marker toggles its effect, unknown markers are kept as text and reported in [`Markup::errors`]
+/// ## Arguments
+/// * `markup` - The output of a widget or a component
+pub fn parse(markup: &str) -> Markup {
+    let mut lines = vec![Line::new()];
+    let mut errors = vec![];
+    let mut style = Style::NONE;
+    let mut text = String::new();
+    // Enabled effects, with the position of the marker which enabled them
+    let mut opened: Vec<(Effect, &str, usize, usize)> = vec![];
+    let (mut line, mut column) = (1, 1);
+
+    let mut rest = markup;
+    while let Some(symbol) = rest.chars().next() {
+        if let Some(marker) = rest.strip_prefix("[[EFFECT_") {
+            let Some((tag, after)) = marker
+                .split_once("]]")
+                .filter(|(tag, _)| !tag.contains(['\n', '[']))
+            else {
+                errors.push(MarkupError {
+                    kind: MarkupErrorKind::UnclosedMarker,
+                    line,
+                    column,
+                });
+                text += "[[EFFECT_";
+                column += 9;
+                rest = marker;
+                continue;
+            };
+
+            if let Some(effect) = Effect::parse(tag) {
+                flush(&mut lines, &mut text, style);
+                if effect.toggle(&mut style) {
+                    opened.retain(|(opened, ..)| !opened.same_kind(effect));
+                    opened.push((effect, tag, line, column));
+                } else {
+                    opened.retain(|(opened, ..)| *opened != effect);
+                }
+            } else {
+                errors.push(MarkupError {
+                    kind: MarkupErrorKind::UnknownTag(String::from(tag)),
+                    line,
+                    column,
+                });
+                text += &rest[..tag.len() + 11];
+            }
+            column += tag.chars().count() + 11;
+            rest = after;
+            continue;
+        }
+
+        if symbol == '\n' {
+            flush(&mut lines, &mut text, style);
+            lines.push(Line::new());
+            line += 1;
+            column = 1;
+        } else {
+            text.push(symbol);
+            column += 1;
+        }
+        rest = &rest[symbol.len_utf8()..];
+    }
+    flush(&mut lines, &mut text, style);
+
+    if (markup.is_empty() || markup.ends_with('\n'))
+        && lines.last().is_some_and(|line| line.spans.is_empty())
+    {
+        lines.pop();
+    }
+
+    errors.extend(
+        opened
+            .into_iter()
+            .map(|(_, tag, line, column)| MarkupError {
+                kind: MarkupErrorKind::Unterminated(String::from(tag)),
+                line,
+                column,
+            }),
+    );
+    errors.sort_by_key(|error| (error.line, error.column));
+
+    Markup { lines, errors }
+}
+
+/// # Check that every marker of a string is valid and closed
+/// ## Arguments
+/// * `markup` - The output of a widget or a component
+pub fn validate(markup: &str) -> Result<(), Vec<MarkupError>> {
+    let markup = parse(markup);
+    if markup.is_valid() {
+        Ok(())
+    } else {
+        Err(markup.errors)
+    }
+}
+
+/// Add the pending text to the last line, as a span of the given style
+fn flush(lines: &mut [Line], text: &mut String, style: Style) {
+    if text.is_empty() {
+        return;
+    }
+    if let Some(line) = lines.last_mut() {
+        line.push(Span::styled(text, style));
+    }
+    text.clear();
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Effect {
+    Bold,
+    Italic,
+    Reverse,
+    Underline,
+    Color(Color, Color),
+}
+
+impl Effect {
+    /// Parse a marker, without its `[[EFFECT_` prefix and `]]` suffix
+    fn parse(tag: &str) -> Option<Self> {
+        match tag {
+            "BOLD" => Some(Self::Bold),
+            "ITALIC" => Some(Self::Italic),
+            "REVERSE" => Some(Self::Reverse),
+            "UNDERLINE" => Some(Self::Underline),
+            _ => {
+                let (fg, bg) = tag.strip_prefix("COLOR_")?.split_once('_')?;
+                Some(Self::Color(Color::from_name(fg)?, Color::from_name(bg)?))
+            }
+        }
+    }
+
+    /// Toggle the effect, return `true` if it is now enabled
+    fn toggle(self, style: &mut Style) -> bool {
+        let modifiers = &mut style.modifiers;
+        let enabled = match self {
+            Self::Bold => &mut modifiers.bold,
+            Self::Italic => &mut modifiers.italic,
+            Self::Reverse => &mut modifiers.reverse,
+            Self::Underline => &mut modifiers.underline,
+            Self::Color(fg, bg) => {
+                let enabled = (style.fg, style.bg) != (fg, bg);
+                (style.fg, style.bg) = if enabled {
+                    (fg, bg)
+                } else {
+                    (Color::Default, Color::Default)
+                };
+                return enabled;
+            }
+        };
+        *enabled = !*enabled;
+        *enabled
+    }
+
+    /// Return `true` if both effects change the same property of the style
+    fn same_kind(self, other: Self) -> bool {
+        matches!((self, other), (Self::Color(..), Self::Color(..))) || self == other
+    }
+}
@@ -0,0 +1,208 @@
+//! Module containing the colors and effects a cell of a [`Frame`](crate::frame::Frame) can be displayed with
+//!
+//! A [`Style`] also writes the `[[EFFECT_*]]` markers understood by Rtop, correctly paired, see [`markup`](crate::markup) to style whole lines.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::style::{Color, Style};
+//!
+//! let style = Style::new().fg(Color::Red).bg(Color::Black).bold();
+//! assert_eq!(
+//!     style.paint("Alert"),
+//!     "[[EFFECT_COLOR_RED_BLACK]][[EFFECT_BOLD]]Alert[[EFFECT_BOLD]][[EFFECT_COLOR_RED_BLACK]]"
+//! );
+//! ```
+
+/// Represent the colors and effects of a cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Style {
+    /// The color of the text
+    pub fg: Color,
+    /// The color of the background
+    pub bg: Color,
+    /// The effects applied to the text
+    pub modifiers: Modifiers,
+}
+
+impl Style {
+    /// Default colors, without any effect
+    pub const NONE: Self = Self {
+        fg: Color::Default,
+        bg: Color::Default,
+        modifiers: Modifiers::NONE,
+    };
+
+    /// # Create a new `Style`, with the default colors and without any effect
+    pub const fn new() -> Self {
+        Self::NONE
+    }
+
+    /// # Set the color of the text
+    pub const fn fg(mut self, color: Color) -> Self {
+        self.fg = color;
+        self
+    }
+
+    /// # Set the color of the background
+    pub const fn bg(mut self, color: Color) -> Self {
+        self.bg = color;
+        self
+    }
+
+    /// # Display the text in bold
+    pub const fn bold(mut self) -> Self {
+        self.modifiers.bold = true;
+        self
+    }
+
+    /// # Display the text in italic
+    pub const fn italic(mut self) -> Self {
+        self.modifiers.italic = true;
+        self
+    }
+
+    /// # Swap the colors of the text and of the background
+    pub const fn reverse(mut self) -> Self {
+        self.modifiers.reverse = true;
+        self
+    }
+
+    /// # Underline the text
+    pub const fn underline(mut self) -> Self {
+        self.modifiers.underline = true;
+        self
+    }
+
+    /// # Return the markers enabling the style, in the order they must be written
+    /// No color marker is written if both colors are [`Color::Default`].
+    /// Otherwise, as Rtop only supports colors by pair, a default text color is written as `WHITE` and a default background color as `BLACK`:
+    /// they are read back as [`Color::White`] and [`Color::Black`] by [`markup::parse`](crate::markup::parse)
+    pub fn markers(&self) -> Vec<String> {
+        let mut markers = vec![];
+        if self.fg != Color::Default || self.bg != Color::Default {
+            markers.push(format!(
+                "[[EFFECT_COLOR_{}_{}]]",
+                self.fg.name().unwrap_or("WHITE"),
+                self.bg.name().unwrap_or("BLACK")
+            ));
+        }
+
+        let modifiers = [
+            (self.modifiers.bold, "[[EFFECT_BOLD]]"),
+            (self.modifiers.italic, "[[EFFECT_ITALIC]]"),
+            (self.modifiers.underline, "[[EFFECT_UNDERLINE]]"),
+            (self.modifiers.reverse, "[[EFFECT_REVERSE]]"),
+        ];
+        for (enabled, marker) in modifiers {
+            if enabled {
+                markers.push(String::from(marker));
+            }
+        }
+        markers
+    }
+
+    /// # Return `text` surrounded by the markers of the style
+    /// The markers are closed in the reverse order they are opened
+    /// ## Arguments
+    /// * `text` - The text to style, on a single line
+    pub fn paint(&self, text: &str) -> String {
+        let markers = self.markers();
+
+        let mut out = markers.concat();
+        out += text;
+        for marker in markers.iter().rev() {
+            out += marker;
+        }
+        out
+    }
+}
+
+/// Represent a color supported by Rtop
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Color {
+    /// The default color of the terminal
+    #[default]
+    Default,
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    /// # Return the name of the color, as used in the `[[EFFECT_COLOR_*]]` markers
+    /// Return `None` for [`Color::Default`]
+    pub fn name(self) -> Option<&'static str> {
+        match self {
+            Self::Default => None,
+            Self::Black => Some("BLACK"),
+            Self::Red => Some("RED"),
+            Self::Green => Some("GREEN"),
+            Self::Yellow => Some("YELLOW"),
+            Self::Blue => Some("BLUE"),
+            Self::Magenta => Some("MAGENTA"),
+            Self::Cyan => Some("CYAN"),
+            Self::White => Some("WHITE"),
+        }
+    }
+
+    /// # Return the color with the given marker name, such as `RED`
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Black,
+            Self::Red,
+            Self::Green,
+            Self::Yellow,
+            Self::Blue,
+            Self::Magenta,
+            Self::Cyan,
+            Self::White,
+        ]
+        .into_iter()
+        .find(|color| color.name() == Some(name))
+    }
+}
+
+impl From<crate::components::bar::Color> for Color {
+    fn from(color: crate::components::bar::Color) -> Self {
+        use crate::components::bar::Color as BarColor;
+
+        match color {
+            BarColor::Red => Self::Red,
+            BarColor::Green => Self::Green,
+            BarColor::Yellow => Self::Yellow,
+            BarColor::Blue => Self::Blue,
+            BarColor::Magenta => Self::Magenta,
+            BarColor::Cyan => Self::Cyan,
+            BarColor::White => Self::White,
+        }
+    }
+}
+
+/// Represent the effects applied to the text of a cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    /// Displayed with `[[EFFECT_BOLD]]`
+    pub bold: bool,
+    /// Displayed with `[[EFFECT_ITALIC]]`
+    pub italic: bool,
+    /// Displayed with `[[EFFECT_REVERSE]]`, swap the foreground and background colors
+    pub reverse: bool,
+    /// Displayed with `[[EFFECT_UNDERLINE]]`
+    pub underline: bool,
+}
+
+impl Modifiers {
+    /// No effect
+    pub const NONE: Self = Self {
+        bold: false,
+        italic: false,
+        reverse: false,
+        underline: false,
+    };
+}
@@ -0,0 +1,360 @@
+//! Module containing a headless harness to test a [`Widget`] without Rtop
+//!
+//! [`TestHarness`] calls the widget methods the same way Rtop does, and returns what the user would see as a [`Screen`].
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::testing::TestHarness;
+//! use rtop_dev::widget::Widget;
+//! use std::time::Duration;
+//!
+//! #[derive(Default)]
+//! struct CounterWidget {
+//!     count: u32,
+//! }
+//!
+//! impl Widget for CounterWidget {
+//!     fn display(&mut self, _height: i32, _width: i32) -> String {
+//!         format!("[[EFFECT_BOLD]]{}[[EFFECT_BOLD]] updates", self.count)
+//!     }
+//!
+//!     fn on_update(&mut self, _elapsed: Duration) {
+//!         self.count += 1;
+//!     }
+//!
+//!     fn on_input(&mut self, key: String) {
+//!         if key == "r" {
+//!             self.count = 0;
+//!         }
+//!     }
+//! }
+//!
+//! let mut harness = TestHarness::new(Box::new(CounterWidget::default()));
+//! harness.ticks(3, Duration::from_secs(1)).unwrap();
+//!
+//! let screen = harness.display(1, 20).unwrap();
+//! assert_eq!(screen.lines, vec!["3 updates"]);
+//! assert!(screen.style_at(0, 0).unwrap().modifiers.bold);
+//! assert!(!screen.style_at(0, 2).unwrap().modifiers.bold);
+//!
+//! harness.inputs(&["r"]);
+//! assert_eq!(harness.display(1, 20).unwrap().text(), "0 updates");
+//! ```
+//!
+//! ## Snapshots
+//! [`assert_snapshot!`](crate::assert_snapshot) compares an output with a snapshot stored in the `snapshots` directory next to the test file.
+//! The snapshot holds the text of the output and a style layer, where every character is replaced by a letter identifying its style.
+//! Snapshots are only written when the [`UPDATE_SNAPSHOTS`] environment variable is set to `1`, a missing snapshot fails the test otherwise.
+//! ```text
+//! -- text --
+//! CPU 98%
+//! -- style --
+//! ....AAA
+//! -- legend --
+//! A: fg=red bg=black bold
+//! ```
+//!
+//! The snapshots of the components are stored in the `tests/snapshots` directory of this crate:
+//! ```rust
+//! use rtop_dev::components::bar::Horizontal;
+//! use rtop_dev::testing::{assert_snapshot, Screen};
+//!
+//! let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots/horizontal_half.snap");
+//! assert_snapshot(path, &Screen::from(Horizontal::new(1, 10, None).display(50.0)));
+//! ```
+
+use crate::event::{KeyEvent, MouseEvent};
+use crate::markup::{self, MarkupError};
+use crate::style::Style;
+use crate::widget::Widget;
+use crate::Error;
+use std::time::Duration;
+
+/// Drive a [`Widget`] through its lifecycle, as Rtop would
+pub struct TestHarness {
+    widget: Box<dyn Widget>,
+    size: Option<(i32, i32)>,
+}
+
+impl TestHarness {
+    /// # Create a new `TestHarness` and call the `init` of the widget
+    /// ## Arguments
+    /// * `widget` - The widget to test
+    pub fn new(mut widget: Box<dyn Widget>) -> Self {
+        widget.init();
+        Self { widget, size: None }
+    }
+
+    /// # Return the tested widget, to call any other method
+    pub fn widget_mut(&mut self) -> &mut dyn Widget {
+        &mut *self.widget
+    }
+
+    /// # Call `try_update` once
+    /// ## Arguments
+    /// * `elapsed` - The time elapsed since the previous update
+    pub fn tick(&mut self, elapsed: Duration) -> Result<(), Error> {
+        self.widget.try_update(elapsed)
+    }
+
+    /// # Call `try_update` several times, stopping at the first error
+    /// ## Arguments
+    /// * `count` - The number of updates
+    /// * `elapsed` - The time elapsed between two updates
+    pub fn ticks(&mut self, count: usize, elapsed: Duration) -> Result<(), Error> {
+        for _ in 0..count {
+            self.tick(elapsed)?;
+        }
+        Ok(())
+    }
+
+    /// # Send keys to the widget through `on_key`
+    /// ## Arguments
+    /// * `keys` - The keys pressed by the user, encoded as described in [`event`](crate::event)
+    pub fn inputs(&mut self, keys: &[&str]) {
+        for key in keys {
+            self.widget.on_key(KeyEvent::parse(key));
+        }
+    }
+
+    /// # Send a mouse event to the widget through `on_mouse`
+    /// ## Arguments
+    /// * `event` - The mouse event, with coordinates relative to the widget
+    pub fn mouse(&mut self, event: MouseEvent) {
+        self.widget.on_mouse(event);
+    }
+
+    /// # Display the widget and return what the user would see
+    /// `on_resize` is called first if the size changed since the previous call
+    /// ## Arguments
+    /// * `height` - The height allocated to the widget
+    /// * `width` - The width allocated to the widget
+    pub fn display(&mut self, height: i32, width: i32) -> Result<Screen, Error> {
+        if self.size != Some((height, width)) {
+            self.size = Some((height, width));
+            self.widget.on_resize(height, width);
+        }
+
+        let output = self.widget.try_display(height, width)?;
+        Ok(Screen::new(&output))
+    }
+}
+
+/// Output of a widget, split into the text and the style of every cell
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Screen {
+    /// The output, as returned by the widget
+    pub markup: String,
+    /// Every line of the output, without markers
+    pub lines: Vec<String>,
+    /// The style of every cell of [`Screen::lines`], a wide character takes two cells
+    pub styles: Vec<Vec<Style>>,
+    /// The invalid markers of the output
+    pub errors: Vec<MarkupError>,
+}
+
+impl Screen {
+    /// # Create a new `Screen` from the output of a widget or a component
+    /// ## Arguments
+    /// * `markup` - The output, using the `[[EFFECT_*]]` markers
+    pub fn new(markup: &str) -> Self {
+        let parsed = markup::parse(markup);
+
+        Self {
+            markup: String::from(markup),
+            lines: parsed.lines.iter().map(markup::Line::text).collect(),
+            styles: parsed
+                .lines
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .flat_map(|span| vec![span.style; span.width()])
+                        .collect()
+                })
+                .collect(),
+            errors: parsed.errors,
+        }
+    }
+
+    /// # Return every line of the output without markers, separated by line breaks
+    pub fn text(&self) -> String {
+        self.lines.join("\n")
+    }
+
+    /// # Return the style of a cell, `None` if there is no character at this position
+    /// ## Arguments
+    /// * `row` - The line of the cell
+    /// * `col` - The column of the cell, according to the display width of the characters before it
+    pub fn style_at(&self, row: usize, col: usize) -> Option<Style> {
+        self.styles.get(row)?.get(col).copied()
+    }
+
+    /// # Return `true` if one of the lines contains `text`
+    pub fn contains(&self, text: &str) -> bool {
+        self.lines.iter().any(|line| line.contains(text))
+    }
+
+    /// # Return the content of the snapshot of the screen
+    /// Characters displayed without any style are shown as `.` in the style layer
+    pub fn to_snapshot(&self) -> String {
+        let mut legend: Vec<Style> = vec![];
+        let mut layer = String::new();
+        for styles in &self.styles {
+            for style in styles {
+                if *style == Style::NONE {
+                    layer.push('.');
+                    continue;
+                }
+
+                let index = legend
+                    .iter()
+                    .position(|known| known == style)
+                    .unwrap_or_else(|| {
+                        legend.push(*style);
+                        legend.len() - 1
+                    });
+                layer.push(LEGEND_SYMBOLS.chars().nth(index).unwrap_or('?'));
+            }
+            layer.push('\n');
+        }
+
+        let mut out = String::from("-- text --\n");
+        for line in &self.lines {
+            out += line;
+            out.push('\n');
+        }
+        out += "-- style --\n";
+        out += &layer;
+        out += "-- legend --\n";
+        for (symbol, style) in LEGEND_SYMBOLS.chars().zip(&legend) {
+            out += &format!("{}: {}\n", symbol, describe(style));
+        }
+        out
+    }
+}
+
+impl From<&str> for Screen {
+    fn from(markup: &str) -> Self {
+        Self::new(markup)
+    }
+}
+
+impl From<String> for Screen {
+    fn from(markup: String) -> Self {
+        Self::new(&markup)
+    }
+}
+
+impl From<&String> for Screen {
+    fn from(markup: &String) -> Self {
+        Self::new(markup)
+    }
+}
+
+/// Environment variable overwriting the snapshots with the current output when set to `1`
+pub const UPDATE_SNAPSHOTS: &str = "RTOP_UPDATE_SNAPSHOTS";
+
+/// Symbols of the styles in the style layer of a snapshot
+const LEGEND_SYMBOLS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
+/// # Compare a screen with the snapshot stored at `path`, used by [`assert_snapshot!`](crate::assert_snapshot)
+/// The snapshot is written, whether it exists or not, if [`UPDATE_SNAPSHOTS`] is set to `1`.<br>
+/// **⚠️ Panic with a diff of the snapshot if it doesn't match, or with the expected content if it doesn't exist**
+/// ## Arguments
+/// * `path` - The path of the snapshot file
+/// * `screen` - The output to compare
+#[track_caller]
+pub fn assert_snapshot<P: AsRef<std::path::Path>>(path: P, screen: &Screen) {
+    let path = path.as_ref();
+    let actual = screen.to_snapshot();
+    let update = std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| value == "1");
+
+    match std::fs::read_to_string(path) {
+        Ok(expected) if expected == actual => {}
+        _ if update => {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir).unwrap();
+            }
+            std::fs::write(path, actual).unwrap();
+        }
+        Ok(expected) => panic!(
+            "snapshot `{}` doesn't match, set {}=1 to update it\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            diff(&expected, &actual)
+        ),
+        Err(_) => panic!(
+            "snapshot `{}` doesn't exist, set {}=1 to create it\n{}",
+            path.display(),
+            UPDATE_SNAPSHOTS,
+            actual
+        ),
+    }
+}
+
+/// # Return the path of a snapshot stored next to a test file, used by [`assert_snapshot!`](crate::assert_snapshot)
+/// ## Arguments
+/// * `manifest_dir` - The directory of the `Cargo.toml` of the tested crate
+/// * `file` - The path of the test file, as returned by `file!()`
+/// * `name` - The name of the snapshot
+pub fn snapshot_path(manifest_dir: &str, file: &str, name: &str) -> std::path::PathBuf {
+    // `file!()` is relative to the workspace root, which can be a parent of the crate directory
+    let manifest_dir = std::path::Path::new(manifest_dir);
+    let root = manifest_dir
+        .ancestors()
+        .find(|dir| dir.join(file).exists())
+        .unwrap_or(manifest_dir);
+
+    root.join(file)
+        .with_file_name("snapshots")
+        .join(format!("{}.snap", name))
+}
+
+/// Describe a style in the legend of a snapshot
+fn describe(style: &Style) -> String {
+    let mut parts = vec![];
+    let colors = [("fg", style.fg), ("bg", style.bg)];
+    for (name, color) in colors {
+        if let Some(color) = color.name() {
+            parts.push(format!("{}={}", name, color.to_lowercase()));
+        }
+    }
+
+    let modifiers = [
+        (style.modifiers.bold, "bold"),
+        (style.modifiers.italic, "italic"),
+        (style.modifiers.underline, "underline"),
+        (style.modifiers.reverse, "reverse"),
+    ];
+    for (enabled, name) in modifiers {
+        if enabled {
+            parts.push(String::from(name));
+        }
+    }
+    parts.join(" ")
+}
+
+/// Return the lines which differ between two snapshots
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<&str>>();
+    let actual = actual.lines().collect::<Vec<&str>>();
+
+    let mut out = String::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(expected), Some(actual)) if expected == actual => {
+                out += &format!("  {}\n", expected);
+            }
+            (expected, actual) => {
+                if let Some(expected) = expected {
+                    out += &format!("- {}\n", expected);
+                }
+                if let Some(actual) = actual {
+                    out += &format!("+ {}\n", actual);
+                }
+            }
+        }
+    }
+    out
+}
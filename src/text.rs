@@ -0,0 +1,89 @@
+//! Module containing the tools to measure, truncate and pad text according to its display width
+//!
+//! A character doesn't always take one cell once displayed: CJK characters and emojis take two cells, and combining marks take none.
+//! Every component uses these functions instead of `len` or `chars().count()` to align its columns.
+//! Control characters, such as line breaks and tabs, are replaced by a space so the text stays on a single line.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::text;
+//!
+//! assert_eq!(text::width("rtop"), 4);
+//! assert_eq!(text::width("日本"), 4);
+//! assert_eq!(text::width("e\u{301}"), 1); // `e` followed by a combining accent
+//!
+//! assert_eq!(text::truncate("日本語", 5), "日本");
+//! assert_eq!(text::pad("日本語", 5), "日本 ");
+//! assert_eq!(text::pad_left("42", 5), "   42");
+//! assert_eq!(text::pad("a\nb", 4), "a b ");
+//! ```
+
+use std::borrow::Cow;
+use unicode_width::UnicodeWidthChar;
+
+/// # Return the number of cells the text takes once displayed, the sum of the `char_width` of its characters
+pub fn width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// # Return the number of cells a character takes once displayed
+/// Control characters take one cell, as they are displayed as a space by `sanitize`, `truncate` and the `pad` functions
+pub fn char_width(symbol: char) -> usize {
+    if symbol.is_control() {
+        return 1;
+    }
+    UnicodeWidthChar::width(symbol).unwrap_or(0)
+}
+
+/// # Replace the control characters of the text by a space
+/// The text is only copied if it contains control characters
+/// ## Arguments
+/// * `text` - The text to sanitize
+pub fn sanitize(text: &str) -> Cow<'_, str> {
+    if text.contains(char::is_control) {
+        Cow::Owned(text.replace(char::is_control, " "))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// # Return the longest start of the text which fits in `width` cells, with its control characters replaced by a space
+/// A wide character which doesn't entirely fit is removed, so the result can be narrower than `width`
+/// ## Arguments
+/// * `text` - The text to truncate
+/// * `width` - The maximal number of cells
+pub fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+    match sanitize(text) {
+        Cow::Borrowed(text) => Cow::Borrowed(truncate_sanitized(text, width)),
+        Cow::Owned(text) => Cow::Owned(String::from(truncate_sanitized(&text, width))),
+    }
+}
+
+fn truncate_sanitized(text: &str, width: usize) -> &str {
+    let mut used = 0;
+    for (index, symbol) in text.char_indices() {
+        used += char_width(symbol);
+        if used > width {
+            return &text[..index];
+        }
+    }
+    text
+}
+
+/// # Return the text truncated or followed by spaces to take exactly `width` cells, see `truncate`
+/// ## Arguments
+/// * `text` - The text to pad
+/// * `width` - The number of cells of the result
+pub fn pad(text: &str, width: usize) -> String {
+    let text = truncate(text, width);
+    format!("{}{}", text, " ".repeat(width - self::width(&text)))
+}
+
+/// # Return the text truncated or preceded by spaces to take exactly `width` cells, see `truncate`
+/// ## Arguments
+/// * `text` - The text to pad
+/// * `width` - The number of cells of the result
+pub fn pad_left(text: &str, width: usize) -> String {
+    let text = truncate(text, width);
+    format!("{}{}", " ".repeat(width - self::width(&text)), text)
+}
@@ -0,0 +1,188 @@
+//! Module containing the version compatibility check between a plugin and Rtop
+//!
+//! Every widget exported through [`ffi`](crate::ffi) automatically embeds the version of `rtop_dev` it was built with, and the range of host versions it supports.
+//! Rtop must call [`check`] before calling any [`Widget`](crate::widget::Widget) method.
+//!
+//! ## Example
+//! ```rust
+//! use rtop_dev::version::{check, Requirement, Version};
+//!
+//! let plugin = Version::current();
+//! assert!(check(plugin, &Requirement::compatible_with(plugin)).is_ok());
+//!
+//! let plugin = Version::new(1, 1, 0); // Built before the breaking changes of rtop_dev 2.0.0
+//! let err = check(plugin, &Requirement::compatible_with(plugin)).unwrap_err();
+//! println!("{}", err); // Print the reason of the incompatibility
+//! ```
+
+use crate::ffi::AbiMismatch;
+
+/// Represent a `major.minor.patch` version
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// Incremented on breaking changes
+    pub major: u32,
+    /// Incremented when features are added
+    pub minor: u32,
+    /// Incremented on bug fixes
+    pub patch: u32,
+}
+
+impl Version {
+    /// # Create a new `Version`
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// # Return the version of `rtop_dev` the current binary is built with
+    pub const fn current() -> Self {
+        Self::new(
+            parse_u32(env!("CARGO_PKG_VERSION_MAJOR")),
+            parse_u32(env!("CARGO_PKG_VERSION_MINOR")),
+            parse_u32(env!("CARGO_PKG_VERSION_PATCH")),
+        )
+    }
+
+    /// # Parse a version such as `1.1.0`, pre-release and build metadata are ignored
+    pub fn parse(value: &str) -> Option<Self> {
+        let core = value.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|part| part.trim().parse::<u32>().ok());
+
+        let version = Self::new(parts.next()??, parts.next()??, parts.next()??);
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Range of host versions a plugin is compatible with, from `min` included to `max` excluded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Requirement {
+    /// The oldest compatible version
+    pub min: Version,
+    /// The first incompatible version
+    pub max: Version,
+}
+
+impl Requirement {
+    /// # Return the range of host versions compatible with a plugin built with `version`
+    /// Follow the semantic versioning rules: the host must have the same major version and be at least as recent.
+    /// For `0.x` versions, the minor version must also match
+    pub const fn compatible_with(version: Version) -> Self {
+        let max = if version.major == 0 {
+            Version::new(0, version.minor + 1, 0)
+        } else {
+            Version::new(version.major + 1, 0, 0)
+        };
+
+        Self { min: version, max }
+    }
+
+    /// # Return `true` if the version is inside the range
+    pub fn contains(&self, version: Version) -> bool {
+        self.min <= version && version < self.max
+    }
+}
+
+impl std::fmt::Display for Requirement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, ">={}, <{}", self.min, self.max)
+    }
+}
+
+/// # Check that a plugin can be used by the current host
+/// ## Arguments
+/// * `plugin` - The version of `rtop_dev` the plugin was built with
+/// * `requirement` - The range of host versions supported by the plugin
+pub fn check(plugin: Version, requirement: &Requirement) -> Result<(), Incompatibility> {
+    let host = Version::current();
+    if requirement.contains(host) {
+        Ok(())
+    } else {
+        Err(Incompatibility::Version {
+            plugin,
+            host,
+            requirement: *requirement,
+        })
+    }
+}
+
+/// Reason why a plugin can't be used by the host
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Incompatibility {
+    /// The plugin was built with another [`ABI_VERSION`](crate::ffi::ABI_VERSION)
+    Abi(AbiMismatch),
+    /// The version of the host is outside the range supported by the plugin
+    Version {
+        /// The version of `rtop_dev` the plugin was built with
+        plugin: Version,
+        /// The version of `rtop_dev` the host was built with
+        host: Version,
+        /// The range of host versions supported by the plugin
+        requirement: Requirement,
+    },
+    /// The version declared by the plugin can't be parsed
+    InvalidVersion(String),
+    /// The [`WidgetVTable`](crate::ffi::WidgetVTable) of the plugin is too small to hold its required functions, holding its size in bytes
+    InvalidVTable(usize),
+}
+
+impl std::fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Abi(err) => err.fmt(f),
+            Self::Version {
+                plugin,
+                host,
+                requirement,
+            } if host < &requirement.min => write!(
+                f,
+                "the plugin is built with rtop_dev {} and requires a host {}, but the host uses rtop_dev {}, update Rtop",
+                plugin, requirement, host
+            ),
+            Self::Version {
+                plugin,
+                host,
+                requirement,
+            } => write!(
+                f,
+                "the plugin is built with rtop_dev {} and requires a host {}, but the host uses rtop_dev {}, rebuild the plugin",
+                plugin, requirement, host
+            ),
+            Self::InvalidVersion(version) => {
+                write!(f, "the plugin declares an invalid rtop_dev version `{}`", version)
+            }
+            Self::InvalidVTable(size) => {
+                write!(f, "the plugin declares an invalid widget vtable of {} bytes", size)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Incompatibility {}
+
+impl From<AbiMismatch> for Incompatibility {
+    fn from(err: AbiMismatch) -> Self {
+        Self::Abi(err)
+    }
+}
+
+const fn parse_u32(value: &str) -> u32 {
+    let bytes = value.as_bytes();
+    let mut out = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        out = out * 10 + (bytes[i] - b'0') as u32;
+        i += 1;
+    }
+    out
+}
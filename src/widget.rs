@@ -1,22 +1,176 @@
 //! Module containing all essentials tools to create a Rtop's plugin
 
+use crate::event::{KeyEvent, MouseEvent};
+use crate::frame::Frame;
+use crate::Error;
+use std::time::Duration;
+
 /// Trait representing a Widget. Must be implemented on your struct to declare it as a Widget.
 pub trait Widget {
     /// # Called every time Rtop need to update the widget display
     fn display(&mut self, height: i32, width: i32) -> String;
 
+    /// # Draw the widget into a [`Frame`] of styled cells, called by Rtop instead of `display` when it supports frames
+    /// The frame has the size allocated to the widget. By default, write the output of `try_display` into the frame.<br>
+    /// Implement it to draw the components directly into the frame with their `render` method
+    fn render(&mut self, frame: &mut Frame) -> Result<(), Error> {
+        let output = self.try_display(frame.height(), frame.width())?;
+        frame.print_markup(0, 0, &output);
+        Ok(())
+    }
+
+    /// # Called before `display`, return `false` if the output of the widget didn't change since the last `display`
+    /// Rtop then reuses the previous output instead of calling `display`. It is always called again after a resize
+    fn needs_redraw(&mut self) -> bool {
+        true
+    }
+
+    /// # `Event` -- Called before `display` when the size allocated to the widget changes, including before the first `display`
+    /// Can be used to resize the components of the widget once instead of on every frame
+    fn on_resize(&mut self, _height: i32, _width: i32) {}
+
+    /// # Fallible version of `display`, called by Rtop instead of it
+    /// By default, forward to `display`. If an error is returned, Rtop display a placeholder describing it instead of the widget, see [`Error::display`]
+    fn try_display(&mut self, height: i32, width: i32) -> Result<String, Error> {
+        Ok(self.display(height, width))
+    }
+
     /// # Called every time `display` is called, Change the title of the window if None is not returned.
     fn title(&mut self) -> Option<String> {
         None
     }
 
-    /// # `Event` -- Called three time per second, even if the widget is not focused or not visible for the user.
-    fn on_update(&mut self) {}
+    /// # `Event` -- Called every `refresh_interval` of the [`WidgetDescriptor`] (three time per second by default), even if the widget is not focused or not visible for the user.
+    /// Use `on_show` and `on_hide` to pause an expensive sampling while the widget is hidden
+    /// ## Arguments
+    /// * `elapsed` - The time elapsed since the previous update, which can be longer than the refresh interval
+    fn on_update(&mut self, _elapsed: Duration) {}
+
+    /// # Fallible version of `on_update`, called by Rtop instead of it
+    /// By default, forward to `on_update`. Return [`Error::Unavailable`] when a data source can't be read, instead of keeping stale data
+    fn try_update(&mut self, elapsed: Duration) -> Result<(), Error> {
+        self.on_update(elapsed);
+        Ok(())
+    }
+
+    /// # Called after every update, change the refresh interval of the widget if None is not returned.
+    /// The interval starts as the `refresh_interval` of the [`WidgetDescriptor`]
+    fn update_interval(&mut self) -> Option<Duration> {
+        None
+    }
 
     /// # `Event` -- Called when an user input somethings when the widget is focused<br>
-    /// ```⚠️ Only called if input is defined to true when creating the widget```
+    /// ```⚠️ Only called if the input of the WidgetDescriptor accept the keyboard```
     fn on_input(&mut self, _key: String) {}
 
+    /// # `Event` -- Called when an user press a key when the widget is focused, with the key already parsed<br>
+    /// By default, forward the key to `on_input`, as received from Rtop if the event has a `raw` value, or encoded as a string otherwise<br>
+    /// ```⚠️ Only called if the input of the WidgetDescriptor accept the keyboard```
+    fn on_key(&mut self, key: KeyEvent) {
+        let raw = key.raw.clone();
+        self.on_input(raw.unwrap_or_else(|| key.to_string()));
+    }
+
+    /// # `Event` -- Called when an user click, scroll or drag with the mouse over the widget<br>
+    /// The coordinates of the event are relative to the widget<br>
+    /// ```⚠️ Only called if the input of the WidgetDescriptor accept the mouse```
+    fn on_mouse(&mut self, _event: MouseEvent) {}
+
     /// # Called once when a widget is loaded. Can be used to init some information
     fn init(&mut self) {}
+
+    /// # `Event` -- Called when the widget gains the focus of the user
+    fn on_focus(&mut self) {}
+
+    /// # `Event` -- Called when the widget loses the focus of the user
+    fn on_blur(&mut self) {}
+
+    /// # `Event` -- Called when the widget becomes visible for the user
+    fn on_show(&mut self) {}
+
+    /// # `Event` -- Called when the widget is no longer visible for the user, `display` won't be called until `on_show`
+    fn on_hide(&mut self) {}
+
+    /// # Called once before the widget is unloaded, including before a hot-reload, where the new instance is only created afterwards. Can be used to release resources
+    /// **⚠️ No other method is called after `on_destroy`**
+    fn on_destroy(&mut self) {}
+
+    /// # Called before the plugin is hot-reloaded, return the state to hand to the new instance of the widget
+    /// The state is an opaque buffer, its format is up to the widget. Return `None` to start from scratch after the reload
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// # Called after `init` on the new instance of a hot-reloaded widget, with the state returned by `save_state`
+    /// **⚠️ The state may come from an older version of the widget, an invalid state must be ignored**
+    fn restore_state(&mut self, _state: &[u8]) {}
+}
+
+/// Default interval between two calls to [`Widget::on_update`]
+pub const DEFAULT_REFRESH_INTERVAL: Duration = Duration::from_millis(333);
+
+/// Describe a [`Widget`] and how Rtop should handle it. Returned by the `init_*` functions of your plugin
+#[non_exhaustive]
+pub struct WidgetDescriptor {
+    /// The widget itself
+    pub widget: Box<dyn Widget>,
+
+    /// The human-readable name of the widget
+    pub name: String,
+
+    /// Define which input the widget should receive from the user
+    pub input: InputMode,
+
+    /// The size Rtop should try to give to the widget, as `(height, width)` in cells
+    pub preferred_size: Option<(i32, i32)>,
+
+    /// The smallest size the widget can be displayed with, as `(height, width)` in cells
+    pub min_size: Option<(i32, i32)>,
+
+    /// Interval between two calls to [`Widget::on_update`], the widget can change it with [`Widget::update_interval`]
+    pub refresh_interval: Duration,
+}
+
+impl WidgetDescriptor {
+    /// # Create a new `WidgetDescriptor`
+    /// The widget will not receive any input, has no size constraint and is updated three times per second
+    /// ## Arguments
+    /// * `widget` - The described widget
+    /// * `name` - The human-readable name of the widget
+    pub fn new(widget: Box<dyn Widget>, name: &str) -> Self {
+        Self {
+            widget,
+            name: String::from(name),
+            input: InputMode::None,
+            preferred_size: None,
+            min_size: None,
+            refresh_interval: DEFAULT_REFRESH_INTERVAL,
+        }
+    }
+}
+
+/// Represent the input a [`Widget`] receive when focused
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InputMode {
+    /// The widget doesn't receive any input
+    None,
+    /// The widget receive keyboard input through [`Widget::on_key`]
+    Keyboard,
+    /// The widget receive mouse input through [`Widget::on_mouse`]
+    Mouse,
+    /// The widget receive both keyboard and mouse input
+    KeyboardAndMouse,
+}
+
+impl InputMode {
+    /// # Return `true` if the widget receive keyboard input
+    pub fn keyboard(self) -> bool {
+        matches!(self, Self::Keyboard | Self::KeyboardAndMouse)
+    }
+
+    /// # Return `true` if the widget receive mouse input
+    pub fn mouse(self) -> bool {
+        matches!(self, Self::Mouse | Self::KeyboardAndMouse)
+    }
 }
@@ -0,0 +1,51 @@
+use rtop_dev::ansi::{self, ColorMode};
+
+const MARKUP: &str = "[[EFFECT_COLOR_RED_BLACK]]Alert[[EFFECT_COLOR_RED_BLACK]] ok\n";
+
+#[test]
+fn ansi16_uses_the_standard_colors() {
+    assert_eq!(
+        ansi::render(MARKUP, ColorMode::Ansi16),
+        "\x1b[31;40mAlert\x1b[0m ok\n"
+    );
+}
+
+#[test]
+fn ansi256_uses_the_colors_which_dont_depend_on_the_theme() {
+    assert_eq!(
+        ansi::render(MARKUP, ColorMode::Ansi256),
+        "\x1b[38;5;160;48;5;16mAlert\x1b[0m ok\n"
+    );
+    assert_eq!(
+        ansi::render(
+            "[[EFFECT_COLOR_WHITE_BLUE]]x[[EFFECT_COLOR_WHITE_BLUE]]",
+            ColorMode::Ansi256
+        ),
+        "\x1b[38;5;254;48;5;21mx\x1b[0m"
+    );
+}
+
+#[test]
+fn truecolor_uses_rgb_values() {
+    assert_eq!(
+        ansi::render(MARKUP, ColorMode::TrueColor),
+        "\x1b[38;2;205;0;0;48;2;0;0;0mAlert\x1b[0m ok\n"
+    );
+}
+
+#[test]
+fn effects_are_combined_with_the_colors() {
+    assert_eq!(
+        ansi::render(
+            "[[EFFECT_BOLD]][[EFFECT_UNDERLINE]]x[[EFFECT_UNDERLINE]][[EFFECT_BOLD]]",
+            ColorMode::Ansi16
+        ),
+        "\x1b[1;4mx\x1b[0m"
+    );
+}
+
+#[test]
+fn escape_sequences_of_the_widget_are_not_written() {
+    let output = ansi::render("a\x1b[2Jb\tc\n", ColorMode::TrueColor);
+    assert_eq!(output, "a [2Jb c\n");
+}
@@ -0,0 +1,36 @@
+use rtop_dev::components::bar::{Horizontal, Vertical};
+use rtop_dev::markup;
+
+fn text(output: &str) -> String {
+    markup::parse(output).text()
+}
+
+#[test]
+fn zero_width_range_is_full_once_reached() {
+    let mut bar = Horizontal::new(1, 10, None);
+    bar.set_range(5.0, 5.0);
+    assert_eq!(text(&bar.display(5.0)), "██████████");
+    assert_eq!(text(&bar.display(4.0)), "          ");
+    assert_eq!(text(&bar.display(6.0)), "█████████▶");
+
+    let mut bar = Vertical::new(2, 2, None);
+    bar.set_range(5.0, 5.0);
+    assert_eq!(text(&bar.display(5.0)), "██\n██");
+}
+
+#[test]
+fn infinite_values_are_clamped() {
+    let bar = Horizontal::new(1, 10, None);
+    assert_eq!(text(&bar.display(f32::INFINITY)), "█████████▶");
+    assert_eq!(text(&bar.display(f32::NEG_INFINITY)), "          ");
+}
+
+#[test]
+fn values_below_the_range_are_empty() {
+    let mut bar = Horizontal::new(1, 10, None);
+    assert_eq!(text(&bar.display(-20.0)), "          ");
+
+    bar.set_range(10.0, 20.0);
+    assert_eq!(text(&bar.display(5.0)), "          ");
+    assert_eq!(text(&bar.display(15.0)), "█████     ");
+}
@@ -0,0 +1,31 @@
+//! Helpers shared by the tests of the `host` feature
+
+use std::path::PathBuf;
+
+/// Build the plugin of `tests/fixtures/plugin` once, and return the path of its library
+pub fn fixture() -> PathBuf {
+    static LIBRARY: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+    LIBRARY
+        .get_or_init(|| {
+            let target_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("fixtures");
+            let status = std::process::Command::new(env!("CARGO"))
+                .args(["build", "--quiet", "--manifest-path"])
+                .arg(concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/tests/fixtures/plugin/Cargo.toml"
+                ))
+                .arg("--target-dir")
+                .arg(&target_dir)
+                .status()
+                .unwrap();
+            assert!(status.success(), "unable to build the fixture plugin");
+
+            target_dir.join("debug").join(format!(
+                "{}rtop_fixture{}",
+                std::env::consts::DLL_PREFIX,
+                std::env::consts::DLL_SUFFIX
+            ))
+        })
+        .clone()
+}
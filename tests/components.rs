@@ -0,0 +1,72 @@
+use rtop_dev::assert_snapshot;
+use rtop_dev::components::bar::{Color, Horizontal, Vertical};
+use rtop_dev::components::chart::Chart;
+use rtop_dev::components::listview::{ListItem, ListView, Ordering};
+use rtop_dev::components::set_size_assertions;
+use std::collections::HashMap;
+
+fn processes() -> Vec<ListItem> {
+    [
+        ("rtop", "12.5", "80M"),
+        ("cargo", "98.0", "1.2G"),
+        ("日本語", "0.3", "4M"),
+    ]
+    .iter()
+    .map(|(name, cpu, memory)| {
+        let mut data = HashMap::new();
+        data.insert(String::from("CPU"), String::from(*cpu));
+        data.insert(String::from("Memory"), String::from(*memory));
+        ListItem::new(name, &data)
+    })
+    .collect()
+}
+
+#[test]
+fn horizontal() {
+    set_size_assertions(true);
+    let mut bar = Horizontal::new(1, 10, None);
+    assert_snapshot!("horizontal_half", bar.display(50.0));
+
+    bar.set_range(0.0, 8.0);
+    assert_snapshot!("horizontal_overflow", bar.display_sized(12.5, 2, 10));
+    assert_snapshot!("horizontal_no_data", bar.display(f32::NAN));
+}
+
+#[test]
+fn vertical() {
+    set_size_assertions(true);
+    let bar = Vertical::new(4, 2, Some(Color::Red));
+    assert_snapshot!("vertical_partial", bar.display(60.0));
+    assert_snapshot!("vertical_overflow", bar.display(120.0));
+}
+
+#[test]
+fn chart() {
+    set_size_assertions(true);
+    let data = [20, 15, 14, 20, 8, 0, 9, 35, 40, 12];
+    let mut chart = Chart::new(8, 4, Some(40), Some(true), Some(String::from(" MB/s")));
+    assert_snapshot!("chart_unit", chart.display(&data));
+
+    chart.show_unit = false;
+    assert_snapshot!("chart", chart.display_sized(&data, 3, 6));
+    assert_snapshot!("chart_empty", chart.display(&[]));
+}
+
+#[test]
+fn listview() {
+    set_size_assertions(true);
+    let mut listview = ListView::new(
+        30,
+        5,
+        &processes(),
+        String::from("Name"),
+        vec![String::from("CPU"), String::from("Memory")],
+        Some(String::from("CPU")),
+        Some(Ordering::Default),
+    );
+    assert_snapshot!("listview_sorted", listview.display());
+
+    listview.next();
+    listview.sort_by(Some(String::from("Name")), Some(Ordering::Inversed));
+    assert_snapshot!("listview_selection", listview.display_sized(3, 20));
+}
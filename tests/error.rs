@@ -0,0 +1,104 @@
+use rtop_dev::ffi::FfiWidget;
+use rtop_dev::style::{Color, Style};
+use rtop_dev::testing::{Screen, TestHarness};
+use rtop_dev::widget::{Widget, WidgetDescriptor};
+use rtop_dev::Error;
+use std::time::Duration;
+
+/// Read a file on every update, and display its content
+struct FileWidget {
+    path: &'static str,
+    content: Option<String>,
+}
+
+impl Widget for FileWidget {
+    fn display(&mut self, height: i32, width: i32) -> String {
+        self.try_display(height, width)
+            .unwrap_or_else(|err| err.display(height, width))
+    }
+
+    fn try_display(&mut self, _height: i32, _width: i32) -> Result<String, Error> {
+        self.content
+            .clone()
+            .ok_or_else(|| Error::Unavailable(String::from("no data yet")))
+    }
+
+    fn try_update(&mut self, _elapsed: Duration) -> Result<(), Error> {
+        self.content = Some(std::fs::read_to_string(self.path)?);
+        Ok(())
+    }
+}
+
+fn missing_file() -> FileWidget {
+    FileWidget {
+        path: "/nonexistent/rtop",
+        content: None,
+    }
+}
+
+#[test]
+fn io_errors_are_converted_with_the_question_mark() {
+    let mut harness = TestHarness::new(Box::new(missing_file()));
+    let err = harness.tick(Duration::from_secs(1)).unwrap_err();
+
+    assert!(matches!(err, Error::Io(_)));
+    assert!(err.to_string().starts_with("I/O error: "));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn failed_display_is_replaced_by_a_placeholder() {
+    let mut harness = TestHarness::new(Box::new(missing_file()));
+    let err = harness.display(2, 40).unwrap_err();
+    assert_eq!(err.to_string(), "Data source unavailable: no data yet");
+
+    let screen = Screen::new(&harness.widget_mut().display(2, 40));
+    assert_eq!(
+        screen.lines,
+        vec![
+            "Data source unavailable: no data yet    ",
+            "                                        "
+        ]
+    );
+    assert_eq!(
+        screen.style_at(0, 0),
+        Some(Style::new().fg(Color::Red).bg(Color::Black))
+    );
+}
+
+#[test]
+fn default_fallible_methods_forward_to_the_infallible_ones() {
+    #[derive(Default)]
+    struct Counter {
+        count: u32,
+    }
+
+    impl Widget for Counter {
+        fn display(&mut self, _height: i32, _width: i32) -> String {
+            format!("count={}", self.count)
+        }
+
+        fn on_update(&mut self, _elapsed: Duration) {
+            self.count += 1;
+        }
+    }
+
+    let mut harness = TestHarness::new(Box::<Counter>::default());
+    harness.ticks(2, Duration::from_secs(1)).unwrap();
+    assert_eq!(harness.display(1, 10).unwrap().lines, vec!["count=2"]);
+}
+
+#[test]
+fn errors_cross_the_library_boundary() {
+    let descriptor = WidgetDescriptor::new(Box::new(missing_file()), "File");
+    let mut widget = FfiWidget::new(descriptor).into_descriptor().unwrap().widget;
+
+    assert_eq!(
+        widget.try_display(1, 40).unwrap_err().to_string(),
+        "Data source unavailable: no data yet"
+    );
+    // Only the message of the other errors crosses the boundary
+    let err = widget.try_update(Duration::from_secs(1)).unwrap_err();
+    assert!(matches!(err, Error::Other(_)));
+    assert!(err.to_string().starts_with("I/O error: "));
+}
@@ -0,0 +1,129 @@
+use rtop_dev::event::{Key, KeyEvent, Modifiers};
+
+const CTRL: Modifiers = Modifiers {
+    ctrl: true,
+    alt: false,
+    shift: false,
+};
+const ALT: Modifiers = Modifiers {
+    ctrl: false,
+    alt: true,
+    shift: false,
+};
+const SHIFT: Modifiers = Modifiers {
+    ctrl: false,
+    alt: false,
+    shift: true,
+};
+
+#[test]
+fn parse_recognizes_every_encoding() {
+    let cases = [
+        ("a", Key::Char('a'), Modifiers::NONE),
+        ("A", Key::Char('A'), SHIFT),
+        ("é", Key::Char('é'), Modifiers::NONE),
+        ("\n", Key::Enter, Modifiers::NONE),
+        ("^J", Key::Enter, Modifiers::NONE),
+        ("^M", Key::Enter, Modifiers::NONE),
+        ("KEY_ENTER", Key::Enter, Modifiers::NONE),
+        ("^I", Key::Tab, Modifiers::NONE),
+        ("KEY_BTAB", Key::Tab, SHIFT),
+        ("^[", Key::Escape, Modifiers::NONE),
+        ("^?", Key::Backspace, Modifiers::NONE),
+        ("^H", Key::Backspace, Modifiers::NONE),
+        ("KEY_BACKSPACE", Key::Backspace, Modifiers::NONE),
+        ("^C", Key::Char('c'), CTRL),
+        ("M-x", Key::Char('x'), ALT),
+        ("KEY_UP", Key::Up, Modifiers::NONE),
+        ("KEY_SR", Key::Up, SHIFT),
+        ("KEY_NPAGE", Key::PageDown, Modifiers::NONE),
+        ("KEY_F(5)", Key::F(5), Modifiers::NONE),
+        (
+            "kUP7",
+            Key::Up,
+            Modifiers {
+                ctrl: true,
+                alt: true,
+                shift: false,
+            },
+        ),
+        ("kDC5", Key::Delete, CTRL),
+    ];
+
+    for (value, key, modifiers) in cases {
+        assert_eq!(
+            KeyEvent::parse(value),
+            KeyEvent::new(key, modifiers),
+            "{:?}",
+            value
+        );
+    }
+}
+
+#[test]
+fn parse_keeps_unknown_keys() {
+    for value in ["KEY_MOUSE", "kUP9", "KEY_F(x)", "^1", ""] {
+        let event = KeyEvent::parse(value);
+        assert_eq!(event.key, Key::Unknown(String::from(value)));
+        assert_eq!(event.raw.as_deref(), Some(value));
+    }
+}
+
+#[test]
+fn parse_keeps_the_raw_value() {
+    for value in ["KEY_ENTER", "^M", "\n", "^?", "^H"] {
+        assert_eq!(KeyEvent::parse(value).raw.as_deref(), Some(value));
+    }
+    assert_eq!(KeyEvent::new(Key::Enter, Modifiers::NONE).raw, None);
+}
+
+#[test]
+fn display_round_trips_through_parse() {
+    let events = [
+        KeyEvent::new(Key::Char('a'), Modifiers::NONE),
+        KeyEvent::new(Key::Char('A'), SHIFT),
+        KeyEvent::new(Key::Char('c'), CTRL),
+        KeyEvent::new(Key::Char('x'), ALT),
+        KeyEvent::new(Key::Enter, Modifiers::NONE),
+        KeyEvent::new(Key::Tab, Modifiers::NONE),
+        KeyEvent::new(Key::Tab, SHIFT),
+        KeyEvent::new(Key::Escape, Modifiers::NONE),
+        KeyEvent::new(Key::Backspace, Modifiers::NONE),
+        KeyEvent::new(Key::Delete, SHIFT),
+        KeyEvent::new(Key::Insert, Modifiers::NONE),
+        KeyEvent::new(Key::Left, SHIFT),
+        KeyEvent::new(Key::End, Modifiers::NONE),
+        KeyEvent::new(Key::PageUp, Modifiers::NONE),
+        KeyEvent::new(Key::F(12), Modifiers::NONE),
+        KeyEvent::new(Key::Right, CTRL),
+        KeyEvent::new(
+            Key::Home,
+            Modifiers {
+                ctrl: true,
+                alt: true,
+                shift: true,
+            },
+        ),
+        KeyEvent::new(Key::Unknown(String::from("KEY_MOUSE")), Modifiers::NONE),
+    ];
+
+    for event in events {
+        assert_eq!(KeyEvent::parse(&event.to_string()), event, "{}", event);
+    }
+}
+
+#[test]
+fn display_writes_the_canonical_encoding() {
+    for (value, encoded) in [
+        ("a", "a"),
+        ("^C", "^C"),
+        ("M-x", "M-x"),
+        ("\n", "^J"),
+        ("KEY_ENTER", "^J"),
+        ("^?", "KEY_BACKSPACE"),
+        ("KEY_F(5)", "KEY_F(5)"),
+        ("kLFT3", "kLFT3"),
+    ] {
+        assert_eq!(KeyEvent::parse(value).to_string(), encoded);
+    }
+}
@@ -0,0 +1,135 @@
+//! Plugin loaded by the tests of the `host` feature
+
+use rtop_dev::widget::Widget;
+use std::time::Duration;
+
+/// Append a lifecycle event to the file named by `RTOP_FIXTURE_LOG`, if set
+fn log(event: &str) {
+    use std::io::Write;
+
+    if let Some(path) = std::env::var_os("RTOP_FIXTURE_LOG") {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        writeln!(file, "{}", event).unwrap();
+    }
+}
+
+struct Counter {
+    count: u32,
+    keys: Vec<String>,
+}
+
+impl Default for Counter {
+    /// Panic if `RTOP_FIXTURE_PANIC` is set, to simulate a broken version of the plugin
+    fn default() -> Self {
+        assert!(
+            std::env::var_os("RTOP_FIXTURE_PANIC").is_none(),
+            "the counter is broken"
+        );
+        Self {
+            count: 0,
+            keys: vec![],
+        }
+    }
+}
+
+impl Widget for Counter {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        format!("count={} keys={}", self.count, self.keys.join(","))
+    }
+
+    fn on_update(&mut self, _elapsed: Duration) {
+        self.count += 1;
+    }
+
+    fn on_input(&mut self, key: String) {
+        self.keys.push(key);
+    }
+
+    fn init(&mut self) {
+        log("init");
+    }
+
+    fn on_show(&mut self) {
+        log("show");
+    }
+
+    fn on_hide(&mut self) {
+        log("hide");
+    }
+
+    fn on_destroy(&mut self) {
+        log("destroy");
+    }
+
+    fn save_state(&mut self) -> Option<Vec<u8>> {
+        log("save");
+        Some(self.count.to_le_bytes().to_vec())
+    }
+
+    fn restore_state(&mut self, state: &[u8]) {
+        log("restore");
+        if let Ok(bytes) = state.try_into() {
+            self.count = u32::from_le_bytes(bytes);
+        }
+    }
+}
+
+#[derive(Default)]
+struct Hello {}
+
+impl Widget for Hello {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        String::from("Hello World RTop!")
+    }
+}
+
+/// Count its updates and its draws, and only needs a redraw after an update or a resize
+#[derive(Default)]
+struct Ticker {
+    updates: u32,
+    draws: u32,
+    dirty: bool,
+}
+
+impl Widget for Ticker {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        self.draws += 1;
+        self.dirty = false;
+        format!("updates={} draws={}", self.updates, self.draws)
+    }
+
+    fn needs_redraw(&mut self) -> bool {
+        self.dirty
+    }
+
+    fn on_resize(&mut self, _height: i32, _width: i32) {
+        self.dirty = true;
+    }
+
+    fn on_update(&mut self, _elapsed: Duration) {
+        self.updates += 1;
+        self.dirty = true;
+    }
+
+    fn update_interval(&mut self) -> Option<Duration> {
+        Some(Duration::from_secs(3600))
+    }
+}
+
+rtop_dev::export_widget!(
+    Counter,
+    name = counter,
+    input = Keyboard,
+    refresh_interval = Duration::ZERO
+);
+rtop_dev::export_widget!(Hello, name = hello, display_name = "Hello");
+rtop_dev::export_widget!(Ticker, name = ticker, refresh_interval = Duration::ZERO);
+rtop_dev::export_manifest!(
+    name = "Fixture",
+    author = "Rtop",
+    widgets = [counter, hello, ticker]
+);
@@ -0,0 +1,73 @@
+use rtop_dev::guard::GuardedWidget;
+use rtop_dev::widget::Widget;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+struct Broken {
+    destroyed: Arc<AtomicBool>,
+}
+
+impl Widget for Broken {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        panic!("Oops");
+    }
+
+    fn on_destroy(&mut self) {
+        self.destroyed.store(true, Ordering::Relaxed);
+        panic!("Oops again");
+    }
+}
+
+#[test]
+fn broken_widgets_are_still_destroyed() {
+    let destroyed = Arc::new(AtomicBool::new(false));
+    let mut widget = GuardedWidget::new(Box::new(Broken {
+        destroyed: Arc::clone(&destroyed),
+    }));
+
+    widget.display(1, 10);
+    assert_eq!(widget.error().unwrap().callback, "display");
+
+    widget.on_destroy();
+    assert!(destroyed.load(Ordering::Relaxed));
+    // The panic which broke the widget is kept
+    assert_eq!(widget.error().unwrap().message, "Oops");
+}
+
+struct Multiline {}
+
+impl Widget for Multiline {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        panic!("first line\nsecond\x1b[31m line");
+    }
+}
+
+#[test]
+fn placeholder_of_a_multi_line_panic_fills_the_widget() {
+    let mut widget = GuardedWidget::new(Box::new(Multiline {}));
+    let output = widget.display(5, 30);
+    assert!(!output.contains('\x1b'));
+
+    let markup = rtop_dev::markup::parse(&output);
+    assert!(markup.errors.is_empty());
+    let rows = markup
+        .lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>();
+    assert_eq!(
+        rows,
+        vec![
+            "The widget panicked in `displa",
+            "y`: first line                ",
+            "second [31m line              ",
+            "                              ",
+            "                              ",
+        ]
+    );
+}
@@ -0,0 +1,181 @@
+#![cfg(feature = "host")]
+
+use rtop_dev::host::{exported_widgets, HostError, LoadedPlugin};
+use rtop_dev::manifest::Manifest;
+use std::time::Duration;
+
+mod common;
+use common::fixture;
+
+#[test]
+fn exported_widgets_reads_the_dynamic_symbols() {
+    let mut names = exported_widgets(fixture()).unwrap();
+    names.sort();
+    assert_eq!(names, vec!["counter", "hello", "ticker"]);
+}
+
+#[test]
+fn exported_widgets_rejects_other_files() {
+    let err = exported_widgets(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml")).unwrap_err();
+    assert!(matches!(err, HostError::Format(_)));
+}
+
+/// Write a copy of the fixture with some bytes of its ELF header replaced, and return its path
+fn patched_fixture(name: &str, offset: usize, bytes: &[u8]) -> std::path::PathBuf {
+    let mut content = std::fs::read(fixture()).unwrap();
+    content[offset..offset + bytes.len()].copy_from_slice(bytes);
+    let path = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    std::fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn exported_widgets_rejects_32_bit_and_big_endian_libraries() {
+    let err = exported_widgets(patched_fixture("elf32.so", 4, &[1])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid plugin: only 64-bit ELF libraries are supported"
+    );
+
+    let err = exported_widgets(patched_fixture("big_endian.so", 5, &[2])).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid plugin: only little-endian ELF libraries are supported"
+    );
+}
+
+#[test]
+fn exported_widgets_rejects_out_of_range_offsets() {
+    let content = std::fs::read(fixture()).unwrap();
+    let read = |offset: usize, len: usize| {
+        let mut bytes = [0; 8];
+        bytes[..len].copy_from_slice(&content[offset..offset + len]);
+        u64::from_le_bytes(bytes) as usize
+    };
+    let (section_offset, section_size) = (read(0x28, 8), read(0x3A, 2));
+    let dynsym = (0..read(0x3C, 2))
+        .map(|index| section_offset + index * section_size)
+        .find(|header| read(header + 4, 4) == 11)
+        .unwrap();
+    let strings = section_offset + read(dynsym + 40, 4) * section_size;
+
+    // Every symbol name offset overflows once added to the offset of the string table
+    let path = patched_fixture("overflow.so", strings + 24, &u64::MAX.to_le_bytes());
+    let err = exported_widgets(path).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid plugin: the ELF library is malformed"
+    );
+}
+
+#[test]
+fn manifest_is_read_from_the_library() {
+    let manifest = Manifest::from_file(fixture()).unwrap();
+    assert_eq!(manifest.name, "Fixture");
+    assert_eq!(manifest.widgets, vec!["counter", "hello", "ticker"]);
+    assert_eq!(manifest.rtop_dev_version, rtop_dev::VERSION);
+}
+
+#[test]
+fn load_creates_every_widget() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    assert_eq!(plugin.widgets().len(), 3);
+
+    let hello = plugin.widget_mut("hello").unwrap();
+    assert_eq!(hello.descriptor().name, "Hello");
+    hello.set_visible(true);
+    assert_eq!(hello.display(1, 20), "Hello World RTop!");
+
+    let counter = plugin.widget_mut("counter").unwrap();
+    counter.set_visible(true);
+    counter.update();
+    counter.update();
+    assert_eq!(counter.display(1, 20), "count=2 keys=");
+}
+
+#[test]
+fn hidden_widgets_are_not_displayed() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    let counter = plugin.widget_mut("counter").unwrap();
+    assert_eq!(counter.display(1, 5), "     \n");
+
+    counter.set_visible(true);
+    assert_eq!(counter.display(1, 20), "count=0 keys=");
+
+    counter.set_visible(false);
+    counter.update();
+    assert_eq!(counter.display(1, 20), "count=0 keys=");
+    assert_eq!(counter.display(2, 3), "   \n   \n");
+}
+
+#[test]
+fn input_reaches_the_widget_as_sent() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    let counter = plugin.widget_mut("counter").unwrap();
+    counter.set_visible(true);
+    for key in ["KEY_ENTER", "^M", "^?", "^H"] {
+        counter.input(key);
+    }
+    assert_eq!(counter.display(1, 40), "count=0 keys=KEY_ENTER,^M,^?,^H");
+}
+
+#[test]
+fn reload_keeps_the_state() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    let counter = plugin.widget_mut("counter").unwrap();
+    counter.set_visible(true);
+    counter.update();
+
+    plugin.reload().unwrap();
+    assert_eq!(
+        plugin.widget_mut("counter").unwrap().display(1, 20),
+        "count=1 keys="
+    );
+}
+
+#[test]
+fn widgets_are_updated_at_their_refresh_interval() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+
+    // The default interval of `hello` isn't elapsed yet
+    let hello = plugin.widget_mut("hello").unwrap();
+    assert!(!hello.update());
+
+    let ticker = plugin.widget_mut("ticker").unwrap();
+    assert_eq!(ticker.descriptor().refresh_interval, Duration::ZERO);
+    assert!(ticker.update());
+    // Replaced by the interval returned by `update_interval`
+    assert_eq!(
+        ticker.descriptor().refresh_interval,
+        Duration::from_secs(3600)
+    );
+    assert!(!ticker.update());
+
+    plugin.update();
+    let ticker = plugin.widget_mut("ticker").unwrap();
+    ticker.set_visible(true);
+    assert_eq!(ticker.display(1, 20), "updates=1 draws=1");
+}
+
+#[test]
+fn output_is_reused_until_the_widget_needs_a_redraw() {
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    let ticker = plugin.widget_mut("ticker").unwrap();
+    ticker.set_visible(true);
+
+    assert_eq!(ticker.display(1, 20), "updates=0 draws=1");
+    assert_eq!(ticker.display(1, 20), "updates=0 draws=1");
+
+    ticker.update();
+    assert_eq!(ticker.display(1, 20), "updates=1 draws=2");
+    assert_eq!(ticker.display(1, 20), "updates=1 draws=2");
+
+    // A resize always redraws the widget
+    assert_eq!(ticker.display(2, 20), "updates=1 draws=3");
+    assert_eq!(ticker.display(2, 20), "updates=1 draws=3");
+
+    // `render` keeps its own cache
+    let frame = ticker.render(1, 20);
+    assert_eq!(frame.to_markup(), "updates=1 draws=4   \n");
+    assert_eq!(ticker.render(1, 20), frame);
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "host")]
+
+use rtop_dev::host::LoadedPlugin;
+
+mod common;
+use common::fixture;
+
+// Alone in its binary, so no other test writes to the lifecycle log
+#[test]
+fn reload_destroys_the_old_widgets_before_creating_the_new_ones() {
+    let log = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("lifecycle.log");
+    std::fs::remove_file(&log).ok();
+    std::env::set_var("RTOP_FIXTURE_LOG", &log);
+
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    plugin.widget_mut("counter").unwrap().set_visible(true);
+    plugin.reload().unwrap();
+    drop(plugin);
+
+    assert_eq!(
+        std::fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .collect::<Vec<&str>>(),
+        vec!["init", "show", "save", "destroy", "init", "restore", "show", "destroy"]
+    );
+}
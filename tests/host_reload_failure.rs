@@ -0,0 +1,44 @@
+#![cfg(feature = "host")]
+
+use rtop_dev::host::{HostError, LoadedPlugin};
+use rtop_dev::Error;
+
+mod common;
+use common::fixture;
+
+// Alone in its binary, so no other test writes to the lifecycle log or breaks the fixture
+#[test]
+fn failed_reload_keeps_the_old_widgets_running() {
+    let log = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("failed_reload.log");
+    std::fs::remove_file(&log).ok();
+    std::env::set_var("RTOP_FIXTURE_LOG", &log);
+
+    let mut plugin = LoadedPlugin::load(fixture()).unwrap();
+    plugin.widget_mut("counter").unwrap().set_visible(true);
+    plugin.update();
+
+    std::env::set_var("RTOP_FIXTURE_PANIC", "1");
+    let err = plugin.reload().unwrap_err();
+    std::env::remove_var("RTOP_FIXTURE_PANIC");
+    match err {
+        HostError::Widget { name, error } => {
+            assert_eq!(name, "counter");
+            match *error {
+                Error::Panic(panic) => assert_eq!(panic.message, "the counter is broken"),
+                error => panic!("unexpected error: {}", error),
+            }
+        }
+        err => panic!("unexpected error: {}", err),
+    }
+
+    let counter = plugin.widget_mut("counter").unwrap();
+    assert!(counter.visible());
+    assert_eq!(counter.display(1, 20), "count=1 keys=");
+    assert_eq!(
+        std::fs::read_to_string(&log)
+            .unwrap()
+            .lines()
+            .collect::<Vec<&str>>(),
+        vec!["init", "show"]
+    );
+}
@@ -0,0 +1,93 @@
+use rtop_dev::components::{check_size, set_size_assertions};
+use rtop_dev::components::listview::{ListItem, ListView};
+use rtop_dev::event::{MouseButton, MouseEvent, MouseKind};
+use std::collections::HashMap;
+
+fn items(names: &[&str]) -> Vec<ListItem> {
+    names
+        .iter()
+        .map(|name| {
+            let mut data = HashMap::new();
+            data.insert(String::from("cpu"), String::from("1.0"));
+            ListItem::new(name, &data)
+        })
+        .collect()
+}
+
+fn listview(items: &[ListItem], cols: i32, rows: i32) -> ListView {
+    set_size_assertions(true);
+    ListView::new(
+        cols,
+        rows,
+        items,
+        String::from("Name"),
+        vec![String::from("cpu")],
+        None,
+        None,
+    )
+}
+
+#[test]
+fn control_characters_stay_on_their_line() {
+    let mut listview = listview(&items(&["first\nsecond", "tab\there"]), 20, 4);
+    let output = listview.display();
+
+    assert_eq!(check_size(&output, 4, 20), Ok(()));
+    assert!(output.contains("first second"));
+    assert!(output.contains("tab here"));
+}
+
+#[test]
+fn resizing_keeps_the_selection_in_range() {
+    let names = (0..10).map(|i| format!("Item {}", i)).collect::<Vec<_>>();
+    let items = items(&names.iter().map(String::as_str).collect::<Vec<_>>());
+
+    let mut resized = listview(&items, 20, 5);
+    resized.to_last();
+    resized.resize(8, 20);
+    let output = resized.display();
+    assert_eq!(check_size(&output, 8, 20), Ok(()));
+    assert!(output.lines().nth(7).unwrap().contains("Item 9"));
+
+    let mut sized = listview(&items, 20, 5);
+    sized.to_last();
+    let output = sized.display_sized(8, 20);
+    assert_eq!(check_size(&output, 8, 20), Ok(()));
+    assert_eq!(sized.select().name, "Item 9");
+
+    let output = sized.display_sized(25, 20);
+    assert!(output.lines().nth(1).unwrap().contains("Item 0"));
+    assert!(output.lines().nth(10).unwrap().contains("Item 9"));
+}
+
+#[test]
+fn removing_items_keeps_the_selection_in_range() {
+    let names = (0..10).map(|i| format!("Item {}", i)).collect::<Vec<_>>();
+    let items = items(&names.iter().map(String::as_str).collect::<Vec<_>>());
+
+    let mut listview = listview(&items, 20, 5);
+    listview.to_last();
+    listview.update_items(&items[..2]);
+    let output = listview.display();
+    assert_eq!(check_size(&output, 5, 20), Ok(()));
+    assert_eq!(listview.select().name, "Item 1");
+}
+
+#[test]
+fn sorting_from_the_header_keeps_the_selected_item() {
+    let names = (0..5).map(|i| format!("Item {}", i)).collect::<Vec<_>>();
+    let items = items(&names.iter().map(String::as_str).collect::<Vec<_>>());
+    let click = |x| MouseEvent::new(MouseKind::Press(MouseButton::Left), x, 0);
+
+    let mut listview = listview(&items, 20, 4);
+    listview.next();
+    assert!(!listview.on_mouse(&click(-1)));
+    assert_eq!(listview.select().name, "Item 1");
+
+    assert!(listview.on_mouse(&click(0)));
+    assert_eq!(listview.select().name, "Item 1");
+    let output = listview.display();
+    assert_eq!(check_size(&output, 4, 20), Ok(()));
+    assert!(output.lines().nth(1).unwrap().contains("Item 3"));
+    assert!(output.lines().nth(3).unwrap().contains("Item 1"));
+}
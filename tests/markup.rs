@@ -0,0 +1,127 @@
+use rtop_dev::frame::Frame;
+use rtop_dev::markup::{self, Line, MarkupError, MarkupErrorKind, Span};
+use rtop_dev::style::{Color, Style};
+
+fn error(kind: MarkupErrorKind, line: usize, column: usize) -> MarkupError {
+    MarkupError { kind, line, column }
+}
+
+#[test]
+fn unknown_tags_are_kept_as_text() {
+    let markup = markup::parse("ab[[EFFECT_BLINK]]c");
+    assert_eq!(markup.text(), "ab[[EFFECT_BLINK]]c");
+    assert_eq!(
+        markup.errors,
+        vec![error(
+            MarkupErrorKind::UnknownTag(String::from("BLINK")),
+            1,
+            3
+        )]
+    );
+    assert_eq!(
+        markup.errors[0].to_string(),
+        "1:3 unknown marker `[[EFFECT_BLINK]]`"
+    );
+}
+
+#[test]
+fn markers_without_end_are_reported() {
+    let markup = markup::parse("日本[[EFFECT_BOLD\n[[EFFECT_ITALIC\n]]");
+    assert_eq!(markup.text(), "日本[[EFFECT_BOLD\n[[EFFECT_ITALIC\n]]");
+    assert_eq!(
+        markup.errors,
+        vec![
+            error(MarkupErrorKind::UnclosedMarker, 1, 3),
+            error(MarkupErrorKind::UnclosedMarker, 2, 1),
+        ]
+    );
+    assert_eq!(markup.errors[0].to_string(), "1:3 `[[EFFECT_` without `]]`");
+}
+
+#[test]
+fn columns_count_characters_not_cells() {
+    let markup = markup::parse("ok\n日本 [[EFFECT_FOO]] x [[EFFECT_ITALIC]]y");
+    assert_eq!(markup.widths(), vec![2, 23]);
+    assert_eq!(
+        markup.errors,
+        vec![
+            error(MarkupErrorKind::UnknownTag(String::from("FOO")), 2, 4),
+            error(MarkupErrorKind::Unterminated(String::from("ITALIC")), 2, 21),
+        ]
+    );
+}
+
+#[test]
+fn default_colors_are_only_kept_by_pair() {
+    let mut frame = Frame::new(1, 3);
+    frame.print(0, 0, "a", Style::NONE);
+    frame.print(0, 1, "b", Style::new().fg(Color::Red).bg(Color::Blue));
+    assert_eq!(Frame::from_markup(&frame.to_markup(), 1, 3), frame);
+
+    frame.print(0, 2, "c", Style::new().fg(Color::Red));
+    let cell = *Frame::from_markup(&frame.to_markup(), 1, 3)
+        .get(0, 2)
+        .unwrap();
+    assert_eq!((cell.fg, cell.bg), (Color::Red, Color::Black));
+}
+
+#[test]
+fn validate_reports_every_invalid_marker() {
+    let line = Line::from(vec![
+        Span::raw("CPU "),
+        Span::styled(
+            "98%",
+            Style::new()
+                .fg(Color::Red)
+                .bg(Color::Black)
+                .bold()
+                .underline(),
+        ),
+    ]);
+    assert_eq!(markup::validate(&line.to_string()), Ok(()));
+    assert_eq!(markup::parse(&line.to_string()).lines, vec![line]);
+
+    let errors = markup::validate("[[EFFECT_COLOR_PINK_BLACK]]a[[EFFECT_BOLD]]b").unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            error(
+                MarkupErrorKind::UnknownTag(String::from("COLOR_PINK_BLACK")),
+                1,
+                1
+            ),
+            error(MarkupErrorKind::Unterminated(String::from("BOLD")), 1, 29),
+        ]
+    );
+    assert_eq!(
+        errors[1].to_string(),
+        "1:29 `[[EFFECT_BOLD]]` is never closed"
+    );
+}
+
+#[test]
+fn default_color_has_no_marker_name() {
+    assert_eq!(Color::Default.name(), None);
+    assert_eq!(Color::from_name("DEFAULT"), None);
+    assert_eq!(Color::from_name("red"), None);
+    assert_eq!(Color::from_name("RED"), Some(Color::Red));
+    assert_eq!(Style::NONE.paint("plain"), "plain");
+}
+
+#[test]
+fn a_new_color_replaces_the_opened_one() {
+    let markup = markup::parse("[[EFFECT_COLOR_RED_BLACK]]a[[EFFECT_COLOR_BLUE_BLACK]]b");
+    assert_eq!(
+        markup.lines[0].spans[1].style,
+        Style::new().fg(Color::Blue).bg(Color::Black)
+    );
+    // Only the color which is still enabled is reported
+    assert_eq!(
+        markup.errors,
+        vec![error(
+            MarkupErrorKind::Unterminated(String::from("COLOR_BLUE_BLACK")),
+            1,
+            28
+        )]
+    );
+}
@@ -0,0 +1,14 @@
+use rtop_dev::testing::{assert_snapshot, Screen, UPDATE_SNAPSHOTS};
+
+#[test]
+fn missing_snapshots_fail_without_being_written() {
+    if std::env::var(UPDATE_SNAPSHOTS).is_ok_and(|value| value == "1") {
+        return;
+    }
+    let path = std::path::PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("snapshots/missing.snap");
+    std::fs::remove_file(&path).ok();
+
+    let result = std::panic::catch_unwind(|| assert_snapshot(&path, &Screen::from("CPU 98%")));
+    assert!(result.is_err());
+    assert!(!path.exists());
+}
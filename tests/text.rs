@@ -0,0 +1,22 @@
+use rtop_dev::text;
+
+#[test]
+fn control_characters_take_one_cell() {
+    assert_eq!(text::char_width('\t'), 1);
+    assert_eq!(text::width("a\tb\n"), 4);
+    assert_eq!(
+        text::width("a\tb\n"),
+        "a\tb\n".chars().map(text::char_width).sum::<usize>()
+    );
+    assert_eq!(text::pad("a\tb", 4), "a b ");
+}
+
+#[test]
+fn truncate_keeps_the_longest_start_which_fits() {
+    assert_eq!(text::truncate("rtop", 10), "rtop");
+    assert_eq!(text::truncate("rtop", 2), "rt");
+    assert_eq!(text::truncate("日本語", 3), "日");
+    assert_eq!(text::truncate("e\u{301}e\u{301}", 1), "e\u{301}");
+    assert_eq!(text::truncate("a\x1b[31mb", 3), "a [");
+    assert_eq!(text::truncate("rtop", 0), "");
+}
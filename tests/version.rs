@@ -0,0 +1,96 @@
+use rtop_dev::ffi::{AbiMismatch, ABI_VERSION};
+use rtop_dev::version::{check, Incompatibility, Requirement, Version};
+
+#[test]
+fn plugins_built_with_the_same_major_version_are_compatible() {
+    let host = Version::current();
+    assert_eq!(check(host, &Requirement::compatible_with(host)), Ok(()));
+
+    let older = Version::new(host.major, 0, 0);
+    assert_eq!(check(older, &Requirement::compatible_with(older)), Ok(()));
+}
+
+#[test]
+fn newer_plugins_ask_to_update_the_host() {
+    let host = Version::current();
+    let plugin = Version::new(host.major, host.minor + 1, 0);
+    let requirement = Requirement::compatible_with(plugin);
+
+    let err = check(plugin, &requirement).unwrap_err();
+    assert_eq!(
+        err,
+        Incompatibility::Version {
+            plugin,
+            host,
+            requirement
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "the plugin is built with rtop_dev {} and requires a host >={}, <{}.0.0, but the host uses rtop_dev {}, update Rtop",
+            plugin,
+            plugin,
+            host.major + 1,
+            host
+        )
+    );
+}
+
+#[test]
+fn plugins_of_a_previous_major_version_ask_to_be_rebuilt() {
+    let host = Version::current();
+    let plugin = Version::new(host.major - 1, 4, 2);
+
+    let err = check(plugin, &Requirement::compatible_with(plugin)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "the plugin is built with rtop_dev {} and requires a host >={}, <{}, but the host uses rtop_dev {}, rebuild the plugin",
+            plugin,
+            plugin,
+            Version::new(host.major, 0, 0),
+            host
+        )
+    );
+}
+
+#[test]
+fn minor_versions_are_breaking_before_1_0() {
+    let requirement = Requirement::compatible_with(Version::new(0, 3, 1));
+    assert_eq!(requirement.max, Version::new(0, 4, 0));
+    assert!(requirement.contains(Version::new(0, 3, 9)));
+    assert!(!requirement.contains(Version::new(0, 4, 0)));
+    assert!(!requirement.contains(Version::new(0, 3, 0)));
+}
+
+#[test]
+fn versions_are_parsed_without_pre_release_and_metadata() {
+    assert_eq!(Version::parse("1.2.3"), Some(Version::new(1, 2, 3)));
+    assert_eq!(
+        Version::parse("1.2.3-beta.1+build"),
+        Some(Version::new(1, 2, 3))
+    );
+    assert_eq!(Version::parse("1.2"), None);
+    assert_eq!(Version::parse("1.2.3.4"), None);
+    assert_eq!(Version::parse("one.2.3"), None);
+}
+
+#[test]
+fn other_incompatibilities_have_a_message() {
+    assert_eq!(
+        Incompatibility::InvalidVersion(String::from("1.x")).to_string(),
+        "the plugin declares an invalid rtop_dev version `1.x`"
+    );
+    assert_eq!(
+        Incompatibility::from(AbiMismatch {
+            expected: ABI_VERSION,
+            found: 1
+        })
+        .to_string(),
+        format!(
+            "incompatible widget ABI version: expected {}, found 1",
+            ABI_VERSION
+        )
+    );
+}
@@ -0,0 +1,52 @@
+use rtop_dev::components::bar::Horizontal;
+use rtop_dev::frame::Frame;
+use rtop_dev::style;
+use rtop_dev::widget::Widget;
+use rtop_dev::Error;
+
+struct Displayed;
+
+impl Widget for Displayed {
+    fn display(&mut self, _height: i32, _width: i32) -> String {
+        String::from("[[EFFECT_BOLD]]CPU[[EFFECT_BOLD]] 98%\nRAM")
+    }
+}
+
+struct Rendered;
+
+impl Widget for Rendered {
+    fn display(&mut self, height: i32, width: i32) -> String {
+        let mut frame = Frame::new(height, width);
+        match self.render(&mut frame) {
+            Ok(()) => frame.to_markup(),
+            Err(err) => err.display(height, width),
+        }
+    }
+
+    fn render(&mut self, frame: &mut Frame) -> Result<(), Error> {
+        Horizontal::new(1, frame.width(), None).render(100.0, frame, 0, 0);
+        Ok(())
+    }
+}
+
+#[test]
+fn render_writes_the_output_of_display_by_default() {
+    let mut frame = Frame::new(2, 8);
+    Displayed.render(&mut frame).unwrap();
+
+    assert_eq!(frame, Frame::from_markup(&Displayed.display(2, 8), 2, 8));
+    assert!(frame.get(0, 0).unwrap().modifiers.bold);
+    assert_eq!(frame.get(1, 2).unwrap().symbol, 'M');
+    assert_eq!(frame.get(1, 3).unwrap().symbol, ' ');
+}
+
+#[test]
+fn display_can_be_built_from_render() {
+    assert_eq!(
+        Rendered.display(2, 3),
+        "[[EFFECT_COLOR_GREEN_BLACK]]███[[EFFECT_COLOR_GREEN_BLACK]]\n   \n"
+    );
+    let mut frame = Frame::new(1, 3);
+    Rendered.render(&mut frame).unwrap();
+    assert_eq!(frame.get(0, 2).unwrap().fg, style::Color::Green);
+}